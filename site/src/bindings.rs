@@ -0,0 +1,83 @@
+use js_sys::{Array, Object, Reflect};
+use uiua::format::{format_str, FormatConfig};
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    backend::{OutputItem, WebBackend},
+    editor::web_env,
+};
+
+/// The result of formatting and running some code
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunResult {
+    /// The formatted code, or the original code if it could not be formatted
+    pub formatted: String,
+    /// The values left on the stack, bottom first
+    pub stack: Vec<String>,
+    /// Anything printed to stdout
+    pub stdout: String,
+    /// The error, if the code failed
+    pub error: Option<String>,
+}
+
+/// Format and run some code without touching the DOM
+pub fn format_and_run(src: &str) -> RunResult {
+    let config = FormatConfig {
+        trailing_newline: false,
+        ..Default::default()
+    };
+    let formatted = format_str(src, &config).map_or_else(|_| src.into(), |f| f.output);
+    let mut env = web_env();
+    let error = env.load_str(&formatted).err().map(|e| e.show(false));
+    let stack = env.take_stack().iter().map(|v| v.show()).collect();
+    let io = env.downcast_backend::<WebBackend>().unwrap();
+    let stdout = (io.stdout.lock().unwrap().iter())
+        .filter_map(|item| match item {
+            OutputItem::String(s) => Some(s.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    RunResult {
+        formatted,
+        stack,
+        stdout,
+        error,
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn run_code_output() {
+    let result = format_and_run("+1 2");
+    assert_eq!(result.formatted, "+1 2");
+    assert_eq!(result.stack, ["3"]);
+    assert_eq!(result.error, None);
+    let result = format_and_run("rev[1 2]");
+    assert_eq!(result.formatted, "⇌[1 2]");
+    assert_eq!(result.stack, ["[2 1]"]);
+    let result = format_and_run("&p \"hi\" 1 2");
+    assert_eq!(result.stack, ["2", "1"]);
+    assert_eq!(result.stdout, "hi");
+    let result = format_and_run("+1");
+    assert!(result.error.is_some());
+}
+
+/// Format and run some code, for calling from JavaScript
+///
+/// Returns an object with `formatted`, `stack`, `stdout`, and `error` fields.
+/// `error` is `null` if the code ran successfully.
+#[wasm_bindgen]
+pub fn run_code(src: &str) -> JsValue {
+    let result = format_and_run(src);
+    let obj = Object::new();
+    let set = |key: &str, value: JsValue| {
+        Reflect::set(&obj, &key.into(), &value).unwrap();
+    };
+    set("formatted", result.formatted.into());
+    let stack: Array = result.stack.into_iter().map(JsValue::from).collect();
+    set("stack", stack.into());
+    set("stdout", result.stdout.into());
+    set("error", result.error.map_or(JsValue::NULL, Into::into));
+    obj.into()
+}
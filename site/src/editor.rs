@@ -1368,13 +1368,18 @@ fn set_code_html(id: &str, code: &str) {
     elem.set_inner_html(&html);
 }
 
+/// Make an environment for running code in the browser
+pub fn web_env() -> Uiua {
+    Uiua::with_backend(WebBackend::default())
+        .with_mode(RunMode::All)
+        .allow_file_writes(true)
+        .with_execution_limit(Duration::from_secs(10))
+}
+
 /// Run code and return the output
 fn run_code(code: &str) -> Vec<OutputItem> {
-    let io = WebBackend::default();
     // Run
-    let mut env = Uiua::with_backend(io)
-        .with_mode(RunMode::All)
-        .with_execution_limit(Duration::from_secs(10));
+    let mut env = web_env();
     let mut error = None;
     let values = match env.load_str(code) {
         Ok(()) => env.take_stack(),
@@ -1,4 +1,5 @@
 mod backend;
+mod bindings;
 mod docs;
 mod editor;
 mod examples;
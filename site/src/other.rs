@@ -205,7 +205,7 @@ s ← ○×τ⊞×f
         <p>"If running code in the native Uiua interpreter, arrays will not be automatically turned into audio."</p>
         <p>"Instead, you must use the "<Prim prim=Sys(SysOp::AudioPlay)/>" system function to play it."</p>
         <p><Prim prim=Sys(SysOp::AudioPlay)/>" should fine on the website as well, but it is not necessary."</p>
-        <Editor example="&ap÷2×¬◿1×4∶±○×τ×55.÷∶⇡×2. &asr"/>
+        <Editor example="&ap÷2×-∶1◿1×4∶±○×τ×55.÷∶⇡×2. &asr"/>
     }
 }
 
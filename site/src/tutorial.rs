@@ -241,6 +241,7 @@ fn TutorialMath() -> impl IntoView {
     let comp_table = primitive_rows([
         Eq, Ne, Lt, Gt, Le, Ge, Min, Max, Floor, Ceil, Round, Sin, Atan,
     ]);
+    let const_table = primitive_rows([Eta, Pi, Tau, Infinity]);
 
     view! {
         <h1>"Math and Comparison"</h1>
@@ -282,6 +283,18 @@ fn TutorialMath() -> impl IntoView {
         <p>"This is not special syntax. All the numbers are pushed to the stack, then the operators work on them."</p>
         <p>"Remember that you can type the names of operators and then run to format them."</p>
         <Editor example="# Click Run to format!\nmax sqrt2 mod10 abs`31" help={&["", "⇡Click⇡"]}/>
+        <p>"There are also a few constants. Like other primitives, you can type their names and they will be formatted."</p>
+        <table class="bordered-table">
+            <tr>
+                <th>"Constant"</th>
+                <th>"ASCII"</th>
+                <th>"Args"</th>
+            </tr>
+            {const_table}
+        </table>
+        <Editor example="# Click Run to format!\n[pi tau eta inf]"/>
+        <p>"Euler's number is available as the named constant "<code>"e"</code>"."</p>
+        <Editor example="e"/>
     }
 }
 
@@ -2,6 +2,7 @@
 
 use std::{borrow::Cow, cmp::Ordering, iter::repeat, mem::take, sync::Arc};
 
+use ecow::EcoVec;
 use tinyvec::tiny_vec;
 
 use crate::{
@@ -9,7 +10,10 @@ use crate::{
     UiuaResult,
 };
 
-use super::{op2_bytes_retry_fill, op_bytes_ref_retry_fill, op_bytes_retry_fill, FillContext};
+use super::{
+    monadic::axis_strides, op2_bytes_retry_fill, op_bytes_ref_retry_fill, op_bytes_retry_fill,
+    FillContext,
+};
 
 impl Value {
     fn coerce_to_functions<T, C: FillContext, E: ToString>(
@@ -121,6 +125,16 @@ impl Value {
             (Value::Char(a), Value::Char(b)) => a.join_impl(b, ctx)?.into(),
             (Value::Byte(a), Value::Num(b)) => a.convert().join_impl(b, ctx)?.into(),
             (Value::Num(a), Value::Byte(b)) => a.join_impl(b.convert(), ctx)?.into(),
+            (Value::Complex(a), Value::Complex(b)) => a.join_impl(b, ctx)?.into(),
+            (Value::Complex(a), Value::Num(b)) => a.join_impl(b.convert(), ctx)?.into(),
+            (Value::Num(a), Value::Complex(b)) => a.convert().join_impl(b, ctx)?.into(),
+            (Value::Complex(a), Value::Byte(b)) => a.join_impl(b.convert(), ctx)?.into(),
+            (Value::Byte(a), Value::Complex(b)) => a.convert().join_impl(b, ctx)?.into(),
+            (Value::Rational(a), Value::Rational(b)) => a.join_impl(b, ctx)?.into(),
+            (Value::Rational(a), Value::Byte(b)) => a.join_impl(b.convert(), ctx)?.into(),
+            (Value::Byte(a), Value::Rational(b)) => a.convert().join_impl(b, ctx)?.into(),
+            (Value::Rational(a), Value::Num(b)) => a.convert().join_impl(b, ctx)?.into(),
+            (Value::Num(a), Value::Rational(b)) => a.join_impl(b.convert(), ctx)?.into(),
             (a, b) => a.coerce_to_functions(
                 b,
                 ctx,
@@ -141,6 +155,16 @@ impl Value {
             (Value::Char(a), Value::Char(b)) => a.append(b, ctx, action)?.into(),
             (Value::Byte(a), Value::Num(b)) => a.convert().append(b, ctx, action)?.into(),
             (Value::Num(a), Value::Byte(b)) => a.append(b.convert(), ctx, action)?.into(),
+            (Value::Complex(a), Value::Complex(b)) => a.append(b, ctx, action)?.into(),
+            (Value::Complex(a), Value::Num(b)) => a.append(b.convert(), ctx, action)?.into(),
+            (Value::Num(a), Value::Complex(b)) => a.convert().append(b, ctx, action)?.into(),
+            (Value::Complex(a), Value::Byte(b)) => a.append(b.convert(), ctx, action)?.into(),
+            (Value::Byte(a), Value::Complex(b)) => a.convert().append(b, ctx, action)?.into(),
+            (Value::Rational(a), Value::Rational(b)) => a.append(b, ctx, action)?.into(),
+            (Value::Rational(a), Value::Byte(b)) => a.append(b.convert(), ctx, action)?.into(),
+            (Value::Byte(a), Value::Rational(b)) => a.convert().append(b, ctx, action)?.into(),
+            (Value::Rational(a), Value::Num(b)) => a.convert().append(b, ctx, action)?.into(),
+            (Value::Num(a), Value::Rational(b)) => a.append(b.convert(), ctx, action)?.into(),
             (a, b) => a.coerce_to_functions(
                 b,
                 ctx,
@@ -151,6 +175,21 @@ impl Value {
     }
 }
 
+#[test]
+fn join_many() {
+    let mut arr = Array::<f64>::default();
+    for i in 0..1000 {
+        arr = arr.join_infallible(Array::from(i as f64));
+    }
+    assert_eq!(arr.shape.as_slice(), [1000]);
+    assert!(arr.data.iter().copied().eq((0..1000).map(|i| i as f64)));
+
+    let env = Uiua::with_native_sys();
+    let a = Array::new(tiny_vec![2, 2], vec![1.0; 4]);
+    let b = Array::new(tiny_vec![2, 3], vec![1.0; 6]);
+    assert!(a.join(b, &env).is_err());
+}
+
 impl<T: ArrayValue> Array<T> {
     pub fn join(self, other: Self, env: &Uiua) -> UiuaResult<Self> {
         self.join_impl(other, env)
@@ -185,6 +224,7 @@ impl<T: ArrayValue> Array<T> {
                     }
                     other.shape
                 };
+                self.data.reserve(other.data.len());
                 self.data.extend(other.data);
                 self.shape = target_shape;
                 self.shape[0] += 1;
@@ -212,9 +252,15 @@ impl<T: ArrayValue> Array<T> {
                             other.format_shape()
                         ))));
                     }
-                    self.data.extend(other.data);
-                    self.shape[0] += other.shape[0];
-                    self
+                    if self.data.is_empty() {
+                        other.shape[0] += self.shape[0];
+                        other
+                    } else {
+                        self.data.reserve(other.data.len());
+                        self.data.extend(other.data);
+                        self.shape[0] += other.shape[0];
+                        self
+                    }
                 }
             }
         };
@@ -281,6 +327,16 @@ impl Value {
             (Value::Char(a), Value::Char(b)) => a.couple_impl(b, ctx)?.into(),
             (Value::Func(a), Value::Func(b)) => a.couple_impl(b, ctx)?.into(),
             (Value::Num(a), Value::Byte(b)) => a.couple_impl(b.convert(), ctx)?.into(),
+            (Value::Complex(a), Value::Complex(b)) => a.couple_impl(b, ctx)?.into(),
+            (Value::Complex(a), Value::Num(b)) => a.couple_impl(b.convert(), ctx)?.into(),
+            (Value::Num(a), Value::Complex(b)) => a.convert().couple_impl(b, ctx)?.into(),
+            (Value::Complex(a), Value::Byte(b)) => a.couple_impl(b.convert(), ctx)?.into(),
+            (Value::Byte(a), Value::Complex(b)) => a.convert().couple_impl(b, ctx)?.into(),
+            (Value::Rational(a), Value::Rational(b)) => a.couple_impl(b, ctx)?.into(),
+            (Value::Rational(a), Value::Byte(b)) => a.couple_impl(b.convert(), ctx)?.into(),
+            (Value::Byte(a), Value::Rational(b)) => a.convert().couple_impl(b, ctx)?.into(),
+            (Value::Rational(a), Value::Num(b)) => a.convert().couple_impl(b, ctx)?.into(),
+            (Value::Num(a), Value::Rational(b)) => a.couple_impl(b.convert(), ctx)?.into(),
             (Value::Byte(a), Value::Num(b)) => a.convert().couple_impl(b, ctx)?.into(),
             (a, b) => a.coerce_to_functions(
                 b,
@@ -295,6 +351,8 @@ impl Value {
             Value::Num(a) => a.uncouple(env).map(|(a, b)| (a.into(), b.into())),
             Value::Byte(a) => a.uncouple(env).map(|(a, b)| (a.into(), b.into())),
             Value::Char(a) => a.uncouple(env).map(|(a, b)| (a.into(), b.into())),
+            Value::Complex(a) => a.uncouple(env).map(|(a, b)| (a.into(), b.into())),
+            Value::Rational(a) => a.uncouple(env).map(|(a, b)| (a.into(), b.into())),
             Value::Func(a) => a.uncouple(env).map(|(a, b)| (a.into(), b.into())),
         }
     }
@@ -419,10 +477,13 @@ impl<T: ArrayValue> Array<T> {
 impl Value {
     pub fn reshape(&mut self, shape: &Self, env: &Uiua) -> UiuaResult {
         if let Ok(n) = shape.as_nat(env, "") {
+            env.check_array_len(n.checked_mul(self.flat_len()))?;
             match self {
                 Value::Num(a) => a.reshape_scalar(n),
                 Value::Byte(a) => a.reshape_scalar(n),
                 Value::Char(a) => a.reshape_scalar(n),
+                Value::Complex(a) => a.reshape_scalar(n),
+                Value::Rational(a) => a.reshape_scalar(n),
                 Value::Func(a) => a.reshape_scalar(n),
             }
         } else {
@@ -435,11 +496,28 @@ impl Value {
                 Value::Num(a) => a.reshape(&target_shape, env),
                 Value::Byte(a) => a.reshape(&target_shape, env),
                 Value::Char(a) => a.reshape(&target_shape, env),
+                Value::Complex(a) => a.reshape(&target_shape, env),
+                Value::Rational(a) => a.reshape(&target_shape, env),
                 Value::Func(a) => a.reshape(&target_shape, env),
             }?
         }
         Ok(())
     }
+    pub fn reshape_exact(&mut self, shape: &Self, env: &Uiua) -> UiuaResult {
+        let target_shape = shape.as_naturals(env, "Shape should be a list of natural numbers")?;
+        let target_len: usize = target_shape.iter().product();
+        if target_len != self.flat_len() {
+            return Err(env.error(format!(
+                "Cannot reshape array with {} elements into shape {}, which has {} elements",
+                self.flat_len(),
+                FormatShape(&target_shape),
+                target_len
+            )));
+        }
+        *self.shape_mut() = target_shape.into_iter().collect();
+        self.validate_shape();
+        Ok(())
+    }
 }
 
 impl<T: ArrayValue> Array<T> {
@@ -488,31 +566,66 @@ impl<T: ArrayValue> Array<T> {
         } else {
             return Err(env.error("Only the first or last dimension can be negative"));
         };
-        let target_len: usize = shape.iter().product();
-        self.shape = shape;
+        let target_len =
+            env.check_array_len((shape.iter()).try_fold(1usize, |len, &dim| len.checked_mul(dim)))?;
         if self.data.len() < target_len {
-            if let Some(fill) = env.fill::<T>() {
-                let start = self.data.len();
-                self.data.modify(|data| {
-                    data.extend(repeat(fill).take(target_len - start));
-                });
-            } else {
-                let start = self.data.len();
-                self.data.modify(|data| {
-                    data.reserve(target_len - data.len());
-                    for i in 0..target_len - start {
-                        data.push(data[i % start].clone());
-                    }
-                });
+            let fill = env.fill::<T>();
+            if !self
+                .data
+                .modify(|data| force_length(data, target_len, fill))
+            {
+                return Err(env.error(format!(
+                    "Cannot reshape an empty array into shape {} outside a fill context",
+                    FormatShape(&shape)
+                )));
             }
         } else {
             self.data.truncate(target_len);
         }
+        self.shape = shape;
         self.validate_shape();
         Ok(())
     }
 }
 
+/// Truncate or extend `data` to exactly `len` elements
+///
+/// New elements are `fill` if it is given, otherwise `data` is repeated cyclically.
+/// An empty `data` with no `fill` cannot be extended, so it is left unchanged
+/// and `false` is returned.
+pub(crate) fn force_length<T: Clone>(data: &mut EcoVec<T>, len: usize, fill: Option<T>) -> bool {
+    let start = data.len();
+    if len <= start {
+        data.truncate(len);
+        return true;
+    }
+    match fill {
+        Some(fill) => data.extend(repeat(fill).take(len - start)),
+        None if start == 0 => return false,
+        None => {
+            data.reserve(len - start);
+            for i in 0..len - start {
+                data.push(data[i % start].clone());
+            }
+        }
+    }
+    true
+}
+
+#[test]
+fn force_length_empty() {
+    let mut data: EcoVec<f64> = EcoVec::new();
+    assert!(!force_length(&mut data, 3, None));
+    assert!(data.is_empty());
+    assert!(force_length(&mut data, 3, Some(0.0)));
+    assert_eq!(*data, [0.0; 3]);
+    let mut data = EcoVec::from([1, 2]);
+    assert!(force_length(&mut data, 5, None));
+    assert_eq!(*data, [1, 2, 1, 2, 1]);
+    assert!(force_length(&mut data, 1, None));
+    assert_eq!(*data, [1]);
+}
+
 impl Value {
     pub fn keep(&self, kept: Self, env: &Uiua) -> UiuaResult<Self> {
         let counts = self.as_naturals(
@@ -525,6 +638,8 @@ impl Value {
                 Value::Num(a) => a.scalar_keep(counts[0]).into(),
                 Value::Byte(a) => a.scalar_keep(counts[0]).into(),
                 Value::Char(a) => a.scalar_keep(counts[0]).into(),
+                Value::Complex(a) => a.scalar_keep(counts[0]).into(),
+                Value::Rational(a) => a.scalar_keep(counts[0]).into(),
                 Value::Func(a) => a.scalar_keep(counts[0]).into(),
             }
         } else {
@@ -532,6 +647,8 @@ impl Value {
                 Value::Num(a) => a.list_keep(&counts, env)?.into(),
                 Value::Byte(a) => a.list_keep(&counts, env)?.into(),
                 Value::Char(a) => a.list_keep(&counts, env)?.into(),
+                Value::Complex(a) => a.list_keep(&counts, env)?.into(),
+                Value::Rational(a) => a.list_keep(&counts, env)?.into(),
                 Value::Func(a) => a.list_keep(&counts, env)?.into(),
             }
         })
@@ -759,6 +876,8 @@ impl Value {
                 |a| Ok(a.pick_shaped(&index_shape, &index_data, env)?.into()),
             )?,
             Value::Char(a) => Value::Char(a.pick_shaped(&index_shape, &index_data, env)?),
+            Value::Complex(a) => Value::Complex(a.pick_shaped(&index_shape, &index_data, env)?),
+            Value::Rational(a) => Value::Rational(a.pick_shaped(&index_shape, &index_data, env)?),
             Value::Func(a) => Value::Func(a.pick_shaped(&index_shape, &index_data, env)?),
         })
     }
@@ -885,6 +1004,8 @@ impl Value {
                 |a| Ok(a.take(&index, env)?.into()),
             )?,
             Value::Char(a) => Value::Char(a.take(&index, env)?),
+            Value::Complex(a) => Value::Complex(a.take(&index, env)?),
+            Value::Rational(a) => Value::Rational(a.take(&index, env)?),
             Value::Func(a) => Value::Func(a.take(&index, env)?),
         })
     }
@@ -897,6 +1018,8 @@ impl Value {
             Value::Num(a) => Value::Num(a.drop(&index, env)?),
             Value::Byte(a) => Value::Byte(a.drop(&index, env)?),
             Value::Char(a) => Value::Char(a.drop(&index, env)?),
+            Value::Complex(a) => Value::Complex(a.drop(&index, env)?),
+            Value::Rational(a) => Value::Rational(a.drop(&index, env)?),
             Value::Func(a) => Value::Func(a.drop(&index, env)?),
         })
     }
@@ -1272,10 +1395,27 @@ impl Value {
             Value::Num(a) => a.rotate(&by, env)?,
             Value::Byte(a) => a.rotate(&by, env)?,
             Value::Char(a) => a.rotate(&by, env)?,
+            Value::Complex(a) => a.rotate(&by, env)?,
+            Value::Rational(a) => a.rotate(&by, env)?,
             Value::Func(a) => a.rotate(&by, env)?,
         }
         Ok(rotated)
     }
+    /// Rotate an array starting at the given axis
+    ///
+    /// Each rotation amount applies to the next axis.
+    pub fn rotate_axis(&self, axis: usize, mut rotated: Self, env: &Uiua) -> UiuaResult<Self> {
+        let by = self.as_indices(env, "Rotation amount must be a list of integers")?;
+        match &mut rotated {
+            Value::Num(a) => a.rotate_axis(&by, axis, env)?,
+            Value::Byte(a) => a.rotate_axis(&by, axis, env)?,
+            Value::Char(a) => a.rotate_axis(&by, axis, env)?,
+            Value::Complex(a) => a.rotate_axis(&by, axis, env)?,
+            Value::Rational(a) => a.rotate_axis(&by, axis, env)?,
+            Value::Func(a) => a.rotate_axis(&by, axis, env)?,
+        }
+        Ok(rotated)
+    }
 }
 
 impl<T: ArrayValue> Array<T> {
@@ -1290,6 +1430,28 @@ impl<T: ArrayValue> Array<T> {
         rotate(by, &self.shape, &mut self.data);
         Ok(())
     }
+    pub fn rotate_axis(&mut self, by: &[isize], axis: usize, env: &Uiua) -> UiuaResult {
+        let rank = self.rank();
+        if axis >= rank {
+            return Err(env.error(format!("Cannot rotate axis {axis} of a rank {rank} array")));
+        }
+        if axis + by.len() > rank {
+            return Err(env.error(format!(
+                "Cannot rotate rank {rank} array starting at axis {axis} \
+                with index of length {}",
+                by.len()
+            )));
+        }
+        let (_, len, inner) = axis_strides(&self.shape, axis);
+        if len * inner == 0 {
+            return Ok(());
+        }
+        let shape = &self.shape[axis..];
+        for block in self.data.chunks_mut(len * inner) {
+            rotate(by, shape, block);
+        }
+        Ok(())
+    }
 }
 
 fn rotate<T>(by: &[isize], shape: &[usize], data: &mut [T]) {
@@ -1355,6 +1517,8 @@ impl Value {
                 |a| Ok(a.select_impl(indices_shape, &indices, env)?.into()),
             )?,
             Value::Char(a) => a.select_impl(indices_shape, &indices, env)?.into(),
+            Value::Complex(a) => a.select_impl(indices_shape, &indices, env)?.into(),
+            Value::Rational(a) => a.select_impl(indices_shape, &indices, env)?.into(),
             Value::Func(a) => a.select_impl(indices_shape, &indices, env)?.into(),
         })
     }
@@ -1523,6 +1687,8 @@ impl Value {
             Value::Num(a) => a.windows(&size_spec, env)?.into(),
             Value::Byte(a) => a.windows(&size_spec, env)?.into(),
             Value::Char(a) => a.windows(&size_spec, env)?.into(),
+            Value::Complex(a) => a.windows(&size_spec, env)?.into(),
+            Value::Rational(a) => a.windows(&size_spec, env)?.into(),
             Value::Func(a) => a.windows(&size_spec, env)?.into(),
         })
     }
@@ -1599,6 +1765,41 @@ impl<T: ArrayValue> Array<T> {
     }
 }
 
+impl Value {
+    pub fn chunks(&self, from: Self, env: &Uiua) -> UiuaResult<Self> {
+        let size = self.as_nat(env, "Chunk size must be a natural number")?;
+        Ok(match from {
+            Value::Num(a) => a.chunks(size, env)?.into(),
+            Value::Byte(a) => a.chunks(size, env)?.into(),
+            Value::Char(a) => a.chunks(size, env)?.into(),
+            Value::Complex(a) => a.chunks(size, env)?.into(),
+            Value::Rational(a) => a.chunks(size, env)?.into(),
+            Value::Func(a) => a.chunks(size, env)?.into(),
+        })
+    }
+}
+
+impl<T: ArrayValue> Array<T> {
+    pub fn chunks(mut self, size: usize, env: &Uiua) -> UiuaResult<Self> {
+        if self.rank() == 0 {
+            return Err(env.error("Cannot chunk a scalar"));
+        }
+        if size == 0 {
+            return Err(env.error("Chunk size cannot be 0"));
+        }
+        let row_count = self.row_count();
+        if !row_count.is_multiple_of(size) {
+            return Err(env.error(format!(
+                "Cannot split {row_count} rows into chunks of size {size}"
+            )));
+        }
+        self.shape[0] = size;
+        self.shape.insert(0, row_count / size);
+        self.validate_shape();
+        Ok(self)
+    }
+}
+
 impl Value {
     pub fn find(&self, searched: &Self, env: &Uiua) -> UiuaResult<Self> {
         Ok(match (self, searched) {
@@ -1621,7 +1822,7 @@ impl Value {
 
 impl<T: ArrayValue> Array<T> {
     pub fn find(&self, searched: &Self, env: &Uiua) -> UiuaResult<Array<u8>> {
-        if self.rank() > searched.rank() || self.row_count() > searched.row_count() {
+        if self.rank() > searched.rank() {
             return Err(env.error(format!(
                 "Cannot search for array of shape {} in array of shape {}",
                 self.format_shape(),
@@ -1635,6 +1836,14 @@ impl<T: ArrayValue> Array<T> {
             searched_for_shape.insert(0, 1);
         }
 
+        // A searched-for array that is too big is never found
+        if (searched_for_shape.iter().zip(&searched.shape)).any(|(a, b)| a > b) {
+            return Ok(Array::new(
+                searched.shape.clone(),
+                vec![0; searched.flat_len()],
+            ));
+        }
+
         // Determine the ouput shape
         let output_shape: Shape = searched
             .shape
@@ -1699,7 +1908,22 @@ impl<T: ArrayValue> Array<T> {
                     continue 'windows;
                 }
             }
-            let arr = Array::new(output_shape, data);
+            // Pad the result to the shape of the searched array
+            let mut padded = vec![0; searched.flat_len()];
+            let mut index = vec![0; output_shape.len()];
+            for found in data {
+                let padded_index =
+                    (index.iter().zip(&searched.shape)).fold(0, |acc, (i, s)| acc * s + i);
+                padded[padded_index] = found;
+                for (i, s) in index.iter_mut().zip(&output_shape).rev() {
+                    *i += 1;
+                    if *i < *s {
+                        break;
+                    }
+                    *i = 0;
+                }
+            }
+            let arr = Array::new(searched.shape.clone(), padded);
             arr.validate_shape();
             break Ok(arr);
         }
@@ -1873,3 +2097,201 @@ impl<T: ArrayValue> Array<T> {
         })
     }
 }
+
+impl Value {
+    fn as_mask(&self, env: &Uiua) -> UiuaResult<Vec<bool>> {
+        let is_bool = |n: f64| n == 0.0 || n == 1.0;
+        Ok(match self {
+            Value::Num(nums) if nums.data.iter().all(|&n| is_bool(n)) => {
+                nums.data.iter().map(|&n| n != 0.0).collect()
+            }
+            Value::Byte(bytes) if bytes.data.iter().all(|&b| b <= 1) => {
+                bytes.data.iter().map(|&b| b != 0).collect()
+            }
+            Value::Num(_) | Value::Byte(_) => {
+                return Err(env.error("Mask must be an array of booleans"))
+            }
+            value => {
+                return Err(env.error(format!(
+                    "Mask must be an array of booleans, but it is {}s",
+                    value.type_name()
+                )))
+            }
+        })
+    }
+    /// Replace the elements of an array where a mask is set
+    pub fn mask_replace(&self, replacement: Self, into: Self, env: &Uiua) -> UiuaResult<Self> {
+        if self.shape() != into.shape() {
+            return Err(env.error(format!(
+                "Cannot use a mask of shape {} to replace elements of an array of shape {}",
+                FormatShape(self.shape()),
+                FormatShape(into.shape())
+            )));
+        }
+        if !into.shape().starts_with(replacement.shape()) {
+            return Err(env.error(format!(
+                "Cannot replace elements of an array of shape {} with an array of shape {}",
+                FormatShape(into.shape()),
+                FormatShape(replacement.shape())
+            )));
+        }
+        self.select_where(replacement, into, env)
+    }
+    /// Choose each element from one of two arrays depending on a mask
+    ///
+    /// The mask and both arrays are broadcast to a common shape.
+    pub(crate) fn select_where(&self, then: Self, otherwise: Self, env: &Uiua) -> UiuaResult<Self> {
+        let mask = self.as_mask(env)?;
+        let mask_shape = self.shape();
+        Ok(match (then, otherwise) {
+            (Value::Num(a), Value::Num(b)) => a.select_where(mask_shape, &mask, b, env)?.into(),
+            (Value::Byte(a), Value::Byte(b)) => a.select_where(mask_shape, &mask, b, env)?.into(),
+            (Value::Char(a), Value::Char(b)) => a.select_where(mask_shape, &mask, b, env)?.into(),
+            (Value::Complex(a), Value::Complex(b)) => {
+                a.select_where(mask_shape, &mask, b, env)?.into()
+            }
+            (Value::Rational(a), Value::Rational(b)) => {
+                a.select_where(mask_shape, &mask, b, env)?.into()
+            }
+            (Value::Num(a), Value::Byte(b)) => {
+                a.select_where(mask_shape, &mask, b.convert(), env)?.into()
+            }
+            (Value::Byte(a), Value::Num(b)) => {
+                a.convert().select_where(mask_shape, &mask, b, env)?.into()
+            }
+            (Value::Complex(a), Value::Num(b)) => {
+                a.select_where(mask_shape, &mask, b.convert(), env)?.into()
+            }
+            (Value::Num(a), Value::Complex(b)) => {
+                a.convert().select_where(mask_shape, &mask, b, env)?.into()
+            }
+            (a, b) => a
+                .coerce_to_functions(
+                    b,
+                    env,
+                    |a, b, env| a.select_where(mask_shape, &mask, b, env),
+                    |a, b| format!("Cannot choose between {a} and {b} arrays"),
+                )?
+                .into(),
+        })
+    }
+}
+
+impl<T: ArrayValue> Array<T> {
+    fn select_where(
+        self,
+        mask_shape: &[usize],
+        mask: &[bool],
+        otherwise: Self,
+        env: &Uiua,
+    ) -> UiuaResult<Self> {
+        let shapes = [mask_shape, &self.shape, &otherwise.shape];
+        let shape = shapes.into_iter().max_by_key(|s| s.len()).unwrap();
+        for s in shapes {
+            if !shape.starts_with(s) {
+                return Err(env.error(format!(
+                    "Cannot broadcast shape {} to shape {}",
+                    FormatShape(s),
+                    FormatShape(shape)
+                )));
+            }
+        }
+        // Each element of an array whose shape is a prefix of the full shape
+        // covers a contiguous run of elements in the result
+        let run = |s: &[usize]| shape[s.len()..].iter().product::<usize>();
+        let (mask_run, then_run, else_run) =
+            (run(mask_shape), run(&self.shape), run(&otherwise.shape));
+        let data: Vec<T> = (0..shape.iter().product())
+            .map(|i| {
+                if mask[i / mask_run] {
+                    self.data[i / then_run].clone()
+                } else {
+                    otherwise.data[i / else_run].clone()
+                }
+            })
+            .collect();
+        Ok(Array::new(shape, data))
+    }
+}
+
+impl Value {
+    /// Solve the linear system `Ax = b`, where this value is `A`
+    pub fn solve(&self, b: &Self, env: &Uiua) -> UiuaResult<Self> {
+        let as_nums = |value: &Value| match value {
+            Value::Num(nums) => Ok(nums.clone()),
+            Value::Byte(bytes) => Ok(bytes.clone().convert()),
+            value => Err(env.error(format!(
+                "Cannot solve a linear system of {}s",
+                value.type_name()
+            ))),
+        };
+        let (a, b) = (as_nums(self)?, as_nums(b)?);
+        if a.rank() != 2 || a.shape[0] != a.shape[1] {
+            return Err(env.error(format!(
+                "Cannot solve a linear system with a matrix of shape {}, \
+                because it is not square",
+                a.format_shape()
+            )));
+        }
+        let n = a.shape[0];
+        if b.shape.as_slice() != [n] {
+            return Err(env.error(format!(
+                "Cannot solve a linear system with a matrix of shape {} \
+                and a vector of shape {}",
+                a.format_shape(),
+                b.format_shape()
+            )));
+        }
+        let x = gaussian_elimination(a.data.to_vec(), b.data.to_vec())
+            .ok_or_else(|| env.error("Cannot solve a linear system with a singular matrix"))?;
+        Ok(Array::from(x).into())
+    }
+}
+
+/// Solve a square system by Gaussian elimination with scaled partial pivoting
+///
+/// `a` is the matrix in row-major order. Returns `None` if it is singular.
+///
+/// Pivots are compared relative to the largest element of their row,
+/// so rows with very different magnitudes do not look singular.
+fn gaussian_elimination(mut a: Vec<f64>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    if n == 0 {
+        return Some(Vec::new());
+    }
+    let mut scales: Vec<f64> = (a.chunks_exact(n))
+        .map(|row| row.iter().fold(0.0f64, |acc, x| acc.max(x.abs())))
+        .collect();
+    for col in 0..n {
+        // Swap the row with the largest pivot relative to its scale into place
+        let relative = |i: usize| a[i * n + col].abs() / scales[i];
+        let pivot_row = (col..n)
+            .max_by(|&i, &j| relative(i).total_cmp(&relative(j)))
+            .unwrap();
+        let pivot = a[pivot_row * n + col];
+        if pivot.is_nan() || pivot.abs() <= scales[pivot_row] * n as f64 * f64::EPSILON {
+            return None;
+        }
+        if pivot_row != col {
+            for k in 0..n {
+                a.swap(col * n + k, pivot_row * n + k);
+            }
+            b.swap(col, pivot_row);
+            scales.swap(col, pivot_row);
+        }
+        for row in col + 1..n {
+            let factor = a[row * n + col] / pivot;
+            for k in col..n {
+                a[row * n + k] -= factor * a[col * n + k];
+            }
+            b[row] -= factor * b[col];
+        }
+    }
+    // Back substitution
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|k| a[row * n + k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row * n + row];
+    }
+    Some(x)
+}
@@ -5,7 +5,7 @@ use std::ops::{Add, Div, Mul, Sub};
 use tinyvec::tiny_vec;
 
 use crate::{
-    algorithm::pervade::bin_pervade_generic,
+    algorithm::{monadic::RangeIter, pervade::bin_pervade_generic},
     array::{Array, ArrayValue, FormatShape, Shape},
     cowslice::cowslice,
     primitive::Primitive,
@@ -51,26 +51,59 @@ pub fn reduce(env: &mut Uiua) -> UiuaResult {
     Ok(())
 }
 
-pub fn fast_reduce<T: ArrayValue + Into<R>, R: ArrayValue>(
+/// Reduce a range without materializing it
+///
+/// This is used for `reduce` directly after `range` with a simple
+/// arithmetic function. Ranges with more than one axis fall back to
+/// the normal path.
+pub fn reduce_range(f: Value, range_span: usize, env: &mut Uiua) -> UiuaResult {
+    crate::profile_function!();
+    env.push_span(range_span, Some(Primitive::Range));
+    let max = env.pop(1)?;
+    let shape = max.range_shape(env)?;
+    env.pop_span();
+    if shape.len() == 1 {
+        let (identity, op): (f64, fn(f64, f64) -> f64) = match f.as_primitive() {
+            Some((Primitive::Add, _)) => (0.0, Add::add),
+            Some((Primitive::Mul, _)) => (1.0, Mul::mul),
+            Some((Primitive::Max, _)) => (f64::NEG_INFINITY, f64::max),
+            Some((Primitive::Min, _)) => (f64::INFINITY, f64::min),
+            _ => unreachable!("reduce_range called with {f}"),
+        };
+        let mut range = RangeIter::new(shape);
+        let mut res = match range.next_cell() {
+            Some(&[first]) => first as f64,
+            _ => identity,
+        };
+        while let Some(&[i]) = range.next_cell() {
+            res = op(res, i as f64);
+        }
+        env.push(res);
+        Ok(())
+    } else {
+        env.push(max.range(env)?);
+        env.push(f);
+        reduce(env)
+    }
+}
+
+/// Reduce a numeric array with a simple function
+///
+/// The loops here run over contiguous data without creating any values,
+/// so that the compiler can vectorize them.
+pub fn fast_reduce<T: ArrayValue + Copy + Into<R>, R: ArrayValue + Copy>(
     mut arr: Array<T>,
     identity: R,
     f: impl Fn(R, T) -> R,
 ) -> Array<R> {
     match arr.shape.len() {
-        0 => Array::new(
-            tiny_vec![],
-            vec![arr.data.into_iter().next().unwrap().into()],
-        ),
+        0 => Array::new(tiny_vec![], vec![arr.data[0].into()]),
         1 => {
-            let mut vals = arr.data.into_iter();
-            Array::new(
-                tiny_vec![],
-                vec![if let Some(acc) = vals.next() {
-                    vals.fold(acc.into(), f)
-                } else {
-                    identity
-                }],
-            )
+            let res = match arr.data.split_first() {
+                Some((&first, rest)) => rest.iter().fold(first.into(), |acc, &x| f(acc, x)),
+                None => identity,
+            };
+            Array::new(tiny_vec![], vec![res])
         }
         _ => {
             let row_len = arr.row_len();
@@ -80,15 +113,12 @@ pub fn fast_reduce<T: ArrayValue + Into<R>, R: ArrayValue>(
                 let data = cowslice![identity; row_len];
                 return Array::new(arr.shape, data);
             }
-            let mut new_data: Vec<R> = arr.data[..row_len]
-                .iter()
-                .cloned()
-                .map(Into::into)
-                .collect();
-            for i in 1..row_count {
-                let start = i * row_len;
-                for j in 0..row_len {
-                    new_data[j] = f(new_data[j].clone(), arr.data[start + j].clone());
+            let mut new_data: Vec<R> = arr.data[..row_len].iter().map(|&x| x.into()).collect();
+            if row_len > 0 {
+                for row in arr.data[row_len..].chunks_exact(row_len) {
+                    for (acc, &x) in new_data.iter_mut().zip(row) {
+                        *acc = f(*acc, x);
+                    }
                 }
             }
             arr.shape.remove(0);
@@ -137,6 +167,13 @@ fn generic_fold1(f: Value, xs: Value, init: Option<Value>, env: &mut Uiua) -> Ui
             }
         }
         2 => {
+            if init.is_none() && xs.rank() == 1 && xs.row_count() == 0 {
+                if let Some(identity) = f.as_primitive().and_then(|(prim, _)| prim.identity_value())
+                {
+                    env.push(identity);
+                    return Ok(());
+                }
+            }
             let mut rows = xs.into_rows();
             let mut acc = init
                 .or_else(|| rows.next())
@@ -726,6 +763,7 @@ pub fn table(env: &mut Uiua) -> UiuaResult {
     let f = env.pop(FunctionArg(1))?;
     let xs = env.pop(ArrayArg(1))?;
     let ys = env.pop(ArrayArg(2))?;
+    env.check_array_len(xs.flat_len().checked_mul(ys.flat_len()))?;
     match (f.as_flipped_primitive(), xs, ys) {
         (Some((prim, flipped)), Value::Num(xs), Value::Num(ys)) => {
             if let Err((xs, ys)) = table_nums(prim, flipped, xs, ys, env) {
@@ -1199,6 +1237,14 @@ impl Value {
                 .partition_groups(markers, env)?
                 .map(Into::into)
                 .collect(),
+            Value::Complex(arr) => arr
+                .partition_groups(markers, env)?
+                .map(Into::into)
+                .collect(),
+            Value::Rational(arr) => arr
+                .partition_groups(markers, env)?
+                .map(Into::into)
+                .collect(),
             Value::Func(arr) => arr
                 .partition_groups(markers, env)?
                 .map(Into::into)
@@ -1251,6 +1297,8 @@ impl Value {
             Value::Num(arr) => arr.group_groups(indices, env)?.map(Into::into).collect(),
             Value::Byte(arr) => arr.group_groups(indices, env)?.map(Into::into).collect(),
             Value::Char(arr) => arr.group_groups(indices, env)?.map(Into::into).collect(),
+            Value::Complex(arr) => arr.group_groups(indices, env)?.map(Into::into).collect(),
+            Value::Rational(arr) => arr.group_groups(indices, env)?.map(Into::into).collect(),
             Value::Func(arr) => arr.group_groups(indices, env)?.map(Into::into).collect(),
         })
     }
@@ -1325,3 +1373,23 @@ where
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fast_reduce_matches_generic() {
+        let mut env = Uiua::with_native_sys().with_seed(0);
+        env.load_str("A ← -0.5 ↯40_25 [⍥⚂1000]\nB ← >0 A").unwrap();
+        for op in ["+", "-", "×", "÷", "↥", "↧"] {
+            for arr in ["A", "⊢A", "B", "⊢B"] {
+                // Adding identity keeps the function off the fast path
+                let code = format!("≍ /{op} {arr} /({op}∘) {arr}");
+                env.load_str(&code).unwrap();
+                let matches = env.pop(1).unwrap();
+                assert_eq!(matches, Value::from(true), "{code}");
+            }
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! Algorithms for performing operations on arrays
 
-use std::convert::Infallible;
+use std::{collections::hash_map::DefaultHasher, convert::Infallible, hash::Hasher};
 
 use crate::{
     array::{Array, ArrayValue, Shape},
@@ -11,7 +11,7 @@ mod dyadic;
 pub mod fork;
 pub(crate) mod invert;
 pub mod loops;
-mod monadic;
+pub mod monadic;
 pub mod pervade;
 
 fn max_shape(a: &[usize], b: &[usize]) -> Shape {
@@ -32,6 +32,16 @@ fn max_shape(a: &[usize], b: &[usize]) -> Shape {
     new_shape
 }
 
+/// Hash a cell of an array
+///
+/// Equal cells always have equal hashes, because `NaN`s and `-0` are
+/// normalized, so cells can be bucketed by hash before being compared.
+pub(crate) fn hash_cell<T: ArrayValue>(cell: &[T]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    cell.iter().for_each(|x| x.array_hash(&mut hasher));
+    hasher.finish()
+}
+
 pub trait FillContext: Copy {
     type Error;
     fn error(self, msg: impl ToString) -> Self::Error;
@@ -1,15 +1,10 @@
 //! Algorithms for monadic array operations
 
-use std::{
-    cmp::Ordering,
-    collections::{BTreeMap, BTreeSet},
-    ptr,
-    sync::Arc,
-};
+use std::{cmp::Ordering, collections::HashMap, ptr, sync::Arc};
 
 use tinyvec::tiny_vec;
 
-use crate::{array::*, value::Value, Uiua, UiuaResult};
+use crate::{algorithm::hash_cell, array::*, grid_fmt::format_num, value::Value, Uiua, UiuaResult};
 
 impl Value {
     pub fn deshape(&mut self) {
@@ -18,17 +13,93 @@ impl Value {
             Array::deshape,
             Array::deshape,
             Array::deshape,
+            Array::deshape,
+            Array::deshape,
         )
     }
     pub fn parse_num(&self, env: &Uiua) -> UiuaResult<Self> {
-        Ok(self
-            .as_string(env, "Parsed array must be a string")?
-            .parse::<f64>()
-            .map_err(|e| env.error(format!("Cannot parse into number: {}", e)))?
-            .into())
+        let s = self.as_string(env, "Parsed array must be a string")?;
+        let parse = |word: &str| {
+            word.replace('¯', "-")
+                .parse::<f64>()
+                .map_err(|e| env.error(format!("Cannot parse into number: {}", e)))
+        };
+        let words: Vec<&str> = s.split_whitespace().collect();
+        Ok(if words.len() > 1 {
+            (words.into_iter().map(parse)).collect::<UiuaResult<Value>>()?
+        } else {
+            parse(s.trim())?.into()
+        })
+    }
+    pub fn utf8(&self, env: &Uiua) -> UiuaResult<Self> {
+        let s = self.as_string(env, "Argument to utf must be a string")?;
+        Ok(s.into_bytes().into())
+    }
+    pub fn inv_utf8(&self, env: &Uiua) -> UiuaResult<Self> {
+        let bytes = self.as_number_list(
+            env,
+            "Argument to inverse utf must be a list of bytes",
+            |n| n.fract() == 0.0 && (0.0..256.0).contains(&n),
+            |n| n as u8,
+        )?;
+        let s = String::from_utf8(bytes).map_err(|e| env.error(format!("Invalid UTF-8: {e}")))?;
+        Ok(s.into())
+    }
+    /// Split Unix timestamps into `[year month day hour minute second]` in UTC
+    pub fn date_parts(&self, env: &Uiua) -> UiuaResult<Self> {
+        let times = self.as_number_array(
+            env,
+            "Dateparts argument must be a number or array of numbers",
+            |_| true,
+            f64::is_finite,
+            |n| n,
+        )?;
+        let mut shape = times.shape.clone();
+        shape.push(6);
+        let mut data = Vec::with_capacity(times.flat_len() * 6);
+        for &time in times.data.iter() {
+            if time.abs() > MAX_DATE_TIME {
+                return Err(env.error(format!(
+                    "Cannot get the date parts of {}, \
+                    because it is too far from the Unix epoch",
+                    format_num(time)
+                )));
+            }
+            data.extend(date_parts(time));
+        }
+        Ok(Array::new(shape, data).into())
     }
 }
 
+/// The largest number of seconds from the Unix epoch that
+/// [`Value::date_parts`] accepts, about 285 million years
+///
+/// Within this range, the day count fits in an `i64` and every
+/// part of the date is exactly representable.
+const MAX_DATE_TIME: f64 = 9_007_199_254_740_992.0;
+
+fn date_parts(time: f64) -> [f64; 6] {
+    const DAY: f64 = 24.0 * 60.0 * 60.0;
+    let days = (time / DAY).floor();
+    let secs = time - days * DAY;
+    let hour = (secs / 3600.0).floor();
+    let minute = ((secs - hour * 3600.0) / 60.0).floor();
+    let second = secs - hour * 3600.0 - minute * 60.0;
+    // Convert days since the epoch to a civil date in the proleptic Gregorian calendar.
+    // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    [year as f64, month as f64, day as f64, hour, minute, second]
+}
+
 impl<T: ArrayValue> Array<T> {
     pub fn deshape(&mut self) {
         self.shape = tiny_vec![self.flat_len()];
@@ -37,20 +108,141 @@ impl<T: ArrayValue> Array<T> {
 
 impl Value {
     pub fn range(&self, env: &Uiua) -> UiuaResult<Self> {
-        let shape = &self.as_naturals(
-            env,
-            "Range max should be a single natural number \
-            or a list of natural numbers",
-        )?;
-        let mut shape = Shape::from(shape.as_slice());
+        let mut shape = self.range_shape(env)?;
         let data = range(&shape, env)?;
         if shape.len() > 1 {
             shape.push(shape.len());
         }
         Ok(Array::new(shape, data).into())
     }
+    /// Get the [`Value::range`] of the shape of this array
+    pub fn indices(&self, env: &Uiua) -> UiuaResult<Self> {
+        let shape: Value = self.shape().iter().copied().collect();
+        shape.range(env)
+    }
+    pub(crate) fn range_shape(&self, env: &Uiua) -> UiuaResult<Shape> {
+        let shape = self.as_naturals(
+            env,
+            "Range max should be a single natural number \
+            or a list of natural numbers",
+        )?;
+        Ok(Shape::from(shape.as_slice()))
+    }
+}
+
+/// An iterator over the index cells of a range, computed on demand
+///
+/// This yields the same rows as [`Value::range`] would put in the
+/// flattened index array, but without allocating it.
+pub struct RangeIter {
+    shape: Shape,
+    cell: Vec<usize>,
+    index: usize,
+    len: usize,
+}
+
+impl RangeIter {
+    pub fn new(shape: Shape) -> Self {
+        let len = shape.iter().product();
+        RangeIter {
+            cell: vec![0; shape.len()],
+            shape,
+            index: 0,
+            len,
+        }
+    }
+    /// Get the next index cell without allocating a [`Value`] for it
+    pub fn next_cell(&mut self) -> Option<&[usize]> {
+        if self.index >= self.len {
+            return None;
+        }
+        let mut rem = self.index;
+        for (i, &dim) in self.shape.iter().enumerate().rev() {
+            self.cell[i] = rem % dim;
+            rem /= dim;
+        }
+        self.index += 1;
+        Some(&self.cell)
+    }
+}
+
+impl Iterator for RangeIter {
+    type Item = Value;
+    fn next(&mut self) -> Option<Self::Item> {
+        let scalar = self.shape.len() == 1;
+        let cell = self.next_cell()?;
+        Some(if scalar {
+            (cell[0] as f64).into()
+        } else {
+            cell.iter().map(|&i| i as f64).collect::<Vec<_>>().into()
+        })
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.index;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for RangeIter {}
+
+#[test]
+fn range_iter_matches_range() {
+    let env = Uiua::with_native_sys();
+    let eager = Value::from(vec![3.0, 4.0]).range(&env).unwrap();
+    let lazy: Vec<Value> = RangeIter::new(tiny_vec![3, 4]).collect();
+    assert_eq!(lazy.len(), 12);
+    assert!(eager.into_rows().flat_map(Value::into_rows).eq(lazy));
+}
+
+#[test]
+fn range_too_large() {
+    let env = Uiua::with_native_sys();
+    for shape in [
+        vec![1e10, 1e10, 1e10],
+        vec![18446744073709551616.0],
+        vec![MAX_RANGE_LEN as f64 + 1.0],
+    ] {
+        let err = Value::from(shape.clone()).range(&env).unwrap_err();
+        assert!(
+            err.to_string().contains("Range too large"),
+            "{shape:?}: {err}"
+        );
+    }
+    assert!(Value::from(vec![0.0, 1e20]).range(&env).is_ok());
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn parallel_sort_matches_sequential() {
+    use rand::prelude::*;
+    let mut rng = SmallRng::seed_from_u64(0);
+    let rows = PARALLEL_SORT_THRESHOLD * 2;
+    // Few distinct values so that there are many equal rows to test stability
+    let data: Vec<f64> = (0..rows * 2).map(|_| rng.gen_range(0..10) as f64).collect();
+    let arr = Array::new(tiny_vec![rows, 2], data);
+    let env = Uiua::with_native_sys();
+    for descending in [false, true] {
+        let mut expected: Vec<usize> = (0..rows).collect();
+        expected.sort_by(|&a, &b| {
+            let ordering = arr.row_slice(a).partial_cmp(arr.row_slice(b)).unwrap();
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        let sorted = if descending {
+            arr.fall(&env).unwrap()
+        } else {
+            arr.rise(&env).unwrap()
+        };
+        assert!(sorted == expected, "descending: {descending}");
+    }
 }
 
+/// The maximum number of elements that [`Value::range`] will allocate
+pub const MAX_RANGE_LEN: usize = u32::MAX as usize;
+
 fn range(shape: &[usize], env: &Uiua) -> UiuaResult<Vec<f64>> {
     if shape.is_empty() {
         return Ok(vec![0.0]);
@@ -58,20 +250,20 @@ fn range(shape: &[usize], env: &Uiua) -> UiuaResult<Vec<f64>> {
     if shape.contains(&0) {
         return Ok(Vec::new());
     }
-    let mut len = shape.len();
-    for &item in shape {
-        let (new, overflow) = len.overflowing_mul(item);
-        if overflow {
-            let len = shape.len() as f64 * shape.iter().map(|d| *d as f64).product::<f64>();
-            return Err(env.error(format!(
-                "Attempting to make a range from shape {} would \
-                create an array with {} elements, which is too large",
-                FormatShape(shape),
-                len
-            )));
-        }
-        len = new;
-    }
+    let len = (shape.iter())
+        .try_fold(shape.len(), |len, &item| len.checked_mul(item))
+        .filter(|&len| len <= MAX_RANGE_LEN);
+    let Some(len) = len else {
+        let len = shape.len() as f64 * shape.iter().map(|d| *d as f64).product::<f64>();
+        return Err(env.error(format!(
+            "Range too large: making a range from shape {} would \
+            create an array with {} elements, but the maximum is {}",
+            FormatShape(shape),
+            len,
+            MAX_RANGE_LEN
+        )));
+    };
+    env.check_array_len(Some(len))?;
     let mut data: Vec<f64> = Vec::with_capacity(len);
     let mut curr = vec![0; shape.len()];
     loop {
@@ -101,6 +293,8 @@ impl Value {
             |a| a.first(env).map(Into::into),
             |a| a.first(env).map(Into::into),
             |a| a.first(env).map(Into::into),
+            |a| a.first(env).map(Into::into),
+            |a| a.first(env).map(Into::into),
         )
     }
     pub fn last(self, env: &Uiua) -> UiuaResult<Self> {
@@ -109,6 +303,8 @@ impl Value {
             |a| a.last(env).map(Into::into),
             |a| a.last(env).map(Into::into),
             |a| a.last(env).map(Into::into),
+            |a| a.last(env).map(Into::into),
+            |a| a.last(env).map(Into::into),
         )
     }
 }
@@ -146,24 +342,54 @@ impl Value {
             Array::reverse,
             Array::reverse,
             Array::reverse,
+            Array::reverse,
+            Array::reverse,
         )
     }
+    /// Reverse the array along the given axis
+    pub fn reverse_axis(&mut self, axis: usize, env: &Uiua) -> UiuaResult {
+        let rank = self.rank();
+        if axis >= rank {
+            return Err(env.error(format!("Cannot reverse axis {axis} of a rank {rank} array")));
+        }
+        self.generic_mut(
+            |a| a.reverse_along(axis),
+            |a| a.reverse_along(axis),
+            |a| a.reverse_along(axis),
+            |a| a.reverse_along(axis),
+            |a| a.reverse_along(axis),
+            |a| a.reverse_along(axis),
+        );
+        Ok(())
+    }
 }
 
 impl<T: ArrayValue> Array<T> {
     pub fn reverse(&mut self) {
-        if self.shape.is_empty() || self.flat_len() == 0 {
+        if self.shape.is_empty() {
             return;
         }
-        let row_count = self.row_count();
-        let row_len = self.row_len();
-        for i in 0..row_count / 2 {
-            let left = i * row_len;
-            let right = (row_count - i - 1) * row_len;
-            let left = &mut self.data[left] as *mut T;
-            let right = &mut self.data[right] as *mut T;
-            unsafe {
-                ptr::swap_nonoverlapping(left, right, row_len);
+        self.reverse_along(0);
+    }
+    /// Reverse the array along an axis
+    ///
+    /// The axis must be less than the rank.
+    pub(crate) fn reverse_along(&mut self, axis: usize) {
+        if self.flat_len() == 0 {
+            return;
+        }
+        let (outer, len, inner) = axis_strides(&self.shape, axis);
+        let data = &mut self.data[..];
+        for o in 0..outer {
+            let block = o * len * inner;
+            for i in 0..len / 2 {
+                let left = block + i * inner;
+                let right = block + (len - i - 1) * inner;
+                let left = &mut data[left] as *mut T;
+                let right = &mut data[right] as *mut T;
+                unsafe {
+                    ptr::swap_nonoverlapping(left, right, inner);
+                }
             }
         }
     }
@@ -176,6 +402,8 @@ impl Value {
             Array::transpose,
             Array::transpose,
             Array::transpose,
+            Array::transpose,
+            Array::transpose,
         )
     }
     pub fn inv_transpose(&mut self) {
@@ -184,6 +412,8 @@ impl Value {
             Array::inv_transpose,
             Array::inv_transpose,
             Array::inv_transpose,
+            Array::inv_transpose,
+            Array::inv_transpose,
         )
     }
 }
@@ -233,12 +463,44 @@ impl<T: ArrayValue> Array<T> {
 
 impl Value {
     pub fn rise(&self, env: &Uiua) -> UiuaResult<Self> {
-        self.generic_ref_env(Array::rise, Array::rise, Array::rise, Array::rise, env)
-            .map(Self::from_iter)
+        self.generic_ref_env(
+            Array::rise,
+            Array::rise,
+            Array::rise,
+            Array::rise,
+            Array::rise,
+            Array::rise,
+            env,
+        )
+        .map(Self::from_iter)
     }
     pub fn fall(&self, env: &Uiua) -> UiuaResult<Self> {
-        self.generic_ref_env(Array::fall, Array::fall, Array::fall, Array::fall, env)
-            .map(Self::from_iter)
+        self.generic_ref_env(
+            Array::fall,
+            Array::fall,
+            Array::fall,
+            Array::fall,
+            Array::fall,
+            Array::fall,
+            env,
+        )
+        .map(Self::from_iter)
+    }
+    pub fn sort(self, env: &Uiua) -> UiuaResult<Self> {
+        self.sort_impl(false, env)
+    }
+    pub fn sort_down(self, env: &Uiua) -> UiuaResult<Self> {
+        self.sort_impl(true, env)
+    }
+    fn sort_impl(self, descending: bool, env: &Uiua) -> UiuaResult<Self> {
+        self.generic_into(
+            |a| a.sort(descending, env).map(Into::into),
+            |a| a.sort(descending, env).map(Into::into),
+            |a| a.sort(descending, env).map(Into::into),
+            |a| a.sort(descending, env).map(Into::into),
+            |a| a.sort(descending, env).map(Into::into),
+            |a| a.sort(descending, env).map(Into::into),
+        )
     }
     pub fn classify(&self, env: &Uiua) -> UiuaResult<Self> {
         self.generic_ref_env(
@@ -246,6 +508,8 @@ impl Value {
             Array::classify,
             Array::classify,
             Array::classify,
+            Array::classify,
+            Array::classify,
             env,
         )
         .map(Self::from_iter)
@@ -256,75 +520,142 @@ impl Value {
             Array::deduplicate,
             Array::deduplicate,
             Array::deduplicate,
+            Array::deduplicate,
+            Array::deduplicate,
         )
     }
 }
 
+/// Split a shape around an axis
+///
+/// Returns the number of blocks before the axis, the length of the axis,
+/// and the number of elements in each cell after the axis.
+pub(crate) fn axis_strides(shape: &[usize], axis: usize) -> (usize, usize, usize) {
+    let outer = shape[..axis].iter().product();
+    let inner = shape[axis + 1..].iter().product();
+    (outer, shape[axis], inner)
+}
+
+/// The number of rows above which rows are sorted in parallel
+#[cfg(feature = "parallel")]
+pub const PARALLEL_SORT_THRESHOLD: usize = 100_000;
+
 impl<T: ArrayValue> Array<T> {
     pub fn rise(&self, env: &Uiua) -> UiuaResult<Vec<usize>> {
         if self.rank() == 0 {
             return Err(env.error("Cannot rise a scalar"));
         }
-        if self.flat_len() == 0 {
-            return Ok(Vec::new());
-        }
-        let mut indices = (0..self.row_count()).collect::<Vec<_>>();
-        indices.sort_by(|&a, &b| {
-            self.row_slice(a)
-                .iter()
-                .zip(self.row_slice(b))
-                .map(|(a, b)| a.array_cmp(b))
-                .find(|x| x != &Ordering::Equal)
-                .unwrap_or(Ordering::Equal)
-        });
-        Ok(indices)
+        Ok(self.sorted_row_indices(false))
     }
     pub fn fall(&self, env: &Uiua) -> UiuaResult<Vec<usize>> {
         if self.rank() == 0 {
             return Err(env.error("Cannot fall a scalar"));
         }
+        Ok(self.sorted_row_indices(true))
+    }
+    /// Sort the rows of the array, ascending or descending
+    pub fn sort(mut self, descending: bool, env: &Uiua) -> UiuaResult<Self> {
+        if self.rank() == 0 {
+            return Err(env.error("Cannot sort a scalar"));
+        }
+        let mut data = Vec::with_capacity(self.flat_len());
+        for i in self.sorted_row_indices(descending) {
+            data.extend_from_slice(self.row_slice(i));
+        }
+        self.data = data.into();
+        Ok(self)
+    }
+    /// Get the indices of the rows in sorted order
+    ///
+    /// The sort is stable, so equal rows keep their relative order.
+    fn sorted_row_indices(&self, descending: bool) -> Vec<usize> {
         if self.flat_len() == 0 {
-            return Ok(Vec::new());
+            return Vec::new();
         }
-        let mut indices = (0..self.row_count()).collect::<Vec<_>>();
-        indices.sort_by(|&a, &b| {
-            self.row_slice(a)
-                .iter()
-                .zip(self.row_slice(b))
-                .map(|(a, b)| a.array_cmp(b).reverse())
+        let cmp = |&a: &usize, &b: &usize| {
+            let ordering = (self.row_slice(a).iter().zip(self.row_slice(b)))
+                .map(|(a, b)| a.array_cmp(b))
                 .find(|x| x != &Ordering::Equal)
-                .unwrap_or(Ordering::Equal)
-        });
-        Ok(indices)
+                .unwrap_or(Ordering::Equal);
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        };
+        let mut indices = (0..self.row_count()).collect::<Vec<_>>();
+        #[cfg(feature = "parallel")]
+        if indices.len() > PARALLEL_SORT_THRESHOLD {
+            use rayon::slice::ParallelSliceMut;
+            indices.par_sort_by(cmp);
+            return indices;
+        }
+        indices.sort_by(cmp);
+        indices
     }
     pub fn classify(&self, env: &Uiua) -> UiuaResult<Vec<usize>> {
         if self.rank() == 0 {
             return Err(env.error("Cannot classify a rank-0 array"));
         }
-        let mut classes = BTreeMap::new();
-        let mut classified = Vec::with_capacity(self.row_count());
-        for row in self.rows() {
-            let new_class = classes.len();
-            let class = *classes.entry(row).or_insert(new_class);
-            classified.push(class);
-        }
-        Ok(classified)
+        Ok(self.row_classes().0)
     }
     pub fn deduplicate(&mut self) {
         if self.rank() == 0 {
             return;
         }
-        let mut deduped = Vec::new();
-        let mut seen = BTreeSet::new();
-        let mut new_len = 0;
-        for row in self.rows() {
-            if seen.insert(row.clone()) {
-                deduped.extend_from_slice(&row.data);
-                new_len += 1;
-            }
+        let (_, firsts) = self.row_classes();
+        let mut deduped = Vec::with_capacity(firsts.len() * self.row_len());
+        for &row in &firsts {
+            deduped.extend_from_slice(self.row_slice(row));
         }
         self.data = deduped.into();
-        self.shape[0] = new_len;
+        self.shape[0] = firsts.len();
+    }
+    /// Get the class of each row and the index of the first row of each class
+    ///
+    /// Rows are bucketed by [`hash_cell`], so only rows with equal hashes are compared.
+    fn row_classes(&self) -> (Vec<usize>, Vec<usize>) {
+        let mut buckets: HashMap<u64, Vec<usize>> = HashMap::new();
+        let mut classes = Vec::with_capacity(self.row_count());
+        let mut firsts = Vec::new();
+        for (i, row) in self.row_slices().enumerate() {
+            let bucket = buckets.entry(hash_cell(row)).or_default();
+            let class = bucket.iter().copied().find(|&class| {
+                let first = self.row_slice(firsts[class]);
+                first.iter().zip(row).all(|(a, b)| a.array_eq(b))
+            });
+            let class = class.unwrap_or_else(|| {
+                bucket.push(firsts.len());
+                firsts.push(i);
+                firsts.len() - 1
+            });
+            classes.push(class);
+        }
+        (classes, firsts)
+    }
+}
+
+#[test]
+fn deduplicate_matches_brute_force() {
+    use rand::prelude::*;
+    let mut rng = SmallRng::seed_from_u64(0);
+    let choices = [0.0, -0.0, 1.0, 2.5, f64::NAN, f64::INFINITY];
+    for _ in 0..20 {
+        let rows = rng.gen_range(0..200);
+        let data: Vec<f64> = (0..rows * 2)
+            .map(|_| *choices.choose(&mut rng).unwrap())
+            .collect();
+        let arr = Array::new(tiny_vec![rows, 2], data);
+        let mut expected: Vec<Array<f64>> = Vec::new();
+        for row in arr.rows() {
+            if !expected.contains(&row) {
+                expected.push(row);
+            }
+        }
+        let mut deduped = arr.clone();
+        deduped.deduplicate();
+        assert_eq!(deduped.row_count(), expected.len());
+        assert!(deduped.rows().eq(expected));
     }
 }
 
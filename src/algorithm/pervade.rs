@@ -8,7 +8,7 @@ use std::{
     slice::{self, Chunks},
 };
 
-use crate::{array::*, Uiua, UiuaError, UiuaResult};
+use crate::{array::*, complex::Complex, rational::Rational, Uiua, UiuaError, UiuaResult};
 
 use super::max_shape;
 
@@ -256,10 +256,10 @@ where
 pub mod not {
     use super::*;
     pub fn num(a: f64) -> f64 {
-        1.0 - a
+        (a == 0.0) as u8 as f64
     }
-    pub fn byte(a: u8) -> f64 {
-        num(a.into())
+    pub fn byte(a: u8) -> u8 {
+        (a == 0) as u8
     }
     pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot negate {a}"))
@@ -274,6 +274,12 @@ pub mod neg {
     pub fn byte(a: u8) -> f64 {
         -f64::from(a)
     }
+    pub fn com(a: Complex) -> Complex {
+        -a
+    }
+    pub fn rat(a: Rational) -> Rational {
+        -a
+    }
     pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot negate {a}"))
     }
@@ -286,10 +292,52 @@ pub mod abs {
     pub fn byte(a: u8) -> u8 {
         a
     }
+    pub fn com(a: Complex) -> f64 {
+        a.abs()
+    }
+    pub fn rat(a: Rational) -> Rational {
+        a.abs()
+    }
     pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot take the absolute value of {a}"))
     }
 }
+pub mod isnan {
+    use super::*;
+    pub fn num(a: f64) -> u8 {
+        a.is_nan().into()
+    }
+    pub fn byte(_: u8) -> u8 {
+        0
+    }
+    pub fn com(a: Complex) -> u8 {
+        (a.re.is_nan() || a.im.is_nan()).into()
+    }
+    pub fn rat(a: Rational) -> u8 {
+        a.is_nan().into()
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot check if {a} is NaN"))
+    }
+}
+pub mod isinf {
+    use super::*;
+    pub fn num(a: f64) -> u8 {
+        a.is_infinite().into()
+    }
+    pub fn byte(_: u8) -> u8 {
+        0
+    }
+    pub fn com(a: Complex) -> u8 {
+        (a.re.is_infinite() || a.im.is_infinite()).into()
+    }
+    pub fn rat(_: Rational) -> u8 {
+        0
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot check if {a} is infinite"))
+    }
+}
 pub mod sign {
     use super::*;
     pub fn num(a: f64) -> f64 {
@@ -304,6 +352,12 @@ pub mod sign {
     pub fn byte(a: u8) -> u8 {
         (a > 0) as u8
     }
+    pub fn com(a: Complex) -> Complex {
+        a.normalize()
+    }
+    pub fn rat(a: Rational) -> Rational {
+        a.signum()
+    }
     pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot get the sign of {a}"))
     }
@@ -316,6 +370,9 @@ pub mod sqrt {
     pub fn byte(a: u8) -> f64 {
         f64::from(a).sqrt()
     }
+    pub fn com(a: Complex) -> Complex {
+        a.sqrt()
+    }
     pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot take the square root of {a}"))
     }
@@ -416,16 +473,53 @@ pub mod round {
         env.error(format!("Cannot get the rounded value of {a}"))
     }
 }
+pub mod halfeven {
+    use super::*;
+    pub fn num(a: f64) -> f64 {
+        a.round_ties_even()
+    }
+    pub fn byte(a: u8) -> u8 {
+        a
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the rounded value of {a}"))
+    }
+}
+pub mod factorial {
+    use super::*;
+    pub fn num(a: f64) -> f64 {
+        let n = a.round();
+        if n > 170.0 {
+            return f64::INFINITY;
+        }
+        (2..=n as u64).map(|i| i as f64).product()
+    }
+    pub fn byte(a: u8) -> f64 {
+        num(a.into())
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the factorial of {a}"))
+    }
+}
+pub mod bitnot {
+    use super::*;
+    pub fn num(a: f64) -> f64 {
+        !(a.round() as i64) as f64
+    }
+    pub fn byte(a: u8) -> f64 {
+        num(a.into())
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the bitwise not of {a}"))
+    }
+}
 
 macro_rules! cmp_impl {
     ($name:ident $eq:tt $ordering:expr) => {
         pub mod $name {
             use super::*;
-            pub fn always_greater<A, B>(_: A, _: B) -> u8 {
-                ($ordering $eq Ordering::Less).into()
-            }
-            pub fn always_less<A, B>(_: A, _: B) -> u8 {
-                ($ordering $eq Ordering::Greater).into()
+            pub fn by_type<A: ArrayValue, B: ArrayValue>(_: A, _: B) -> u8 {
+                (B::TYPE_ORDER.cmp(&A::TYPE_ORDER) $eq $ordering).into()
             }
             pub fn num_num(a: f64, b: f64) -> u8 {
                 (b.array_cmp(&a) $eq $ordering) as u8
@@ -436,6 +530,33 @@ macro_rules! cmp_impl {
             pub fn num_byte(a: f64, b: u8) -> u8 {
                 (f64::from(b).array_cmp(&a) $eq $ordering) as u8
             }
+            pub fn com_com(a: Complex, b: Complex) -> u8 {
+                (b.array_cmp(&a) $eq $ordering) as u8
+            }
+            pub fn com_num(a: Complex, b: f64) -> u8 {
+                com_com(a, b.into())
+            }
+            pub fn num_com(a: f64, b: Complex) -> u8 {
+                com_com(a.into(), b)
+            }
+            pub fn com_byte(a: Complex, b: u8) -> u8 {
+                com_com(a, b.into())
+            }
+            pub fn byte_com(a: u8, b: Complex) -> u8 {
+                com_com(a.into(), b)
+            }
+            pub fn rat_num(a: Rational, b: f64) -> u8 {
+                num_num(a.to_f64(), b)
+            }
+            pub fn num_rat(a: f64, b: Rational) -> u8 {
+                num_num(a, b.to_f64())
+            }
+            pub fn rat_byte(a: Rational, b: u8) -> u8 {
+                generic(a, b.into())
+            }
+            pub fn byte_rat(a: u8, b: Rational) -> u8 {
+                generic(a.into(), b)
+            }
             pub fn generic<T: Ord>(a: T, b: T) -> u8 {
                 (b.cmp(&a) $eq $ordering).into()
             }
@@ -446,6 +567,37 @@ macro_rules! cmp_impl {
     };
 }
 
+pub mod real {
+    use super::*;
+    pub fn num(a: f64) -> f64 {
+        a
+    }
+    pub fn byte(a: u8) -> u8 {
+        a
+    }
+    pub fn com(a: Complex) -> f64 {
+        a.re
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the real part of {a}"))
+    }
+}
+pub mod imag {
+    use super::*;
+    pub fn num(_: f64) -> f64 {
+        0.0
+    }
+    pub fn byte(_: u8) -> u8 {
+        0
+    }
+    pub fn com(a: Complex) -> f64 {
+        a.im
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the imaginary part of {a}"))
+    }
+}
+
 cmp_impl!(is_eq == std::cmp::Ordering::Equal);
 cmp_impl!(is_ne != Ordering::Equal);
 cmp_impl!(is_lt == Ordering::Less);
@@ -480,6 +632,36 @@ pub mod add {
     pub fn char_byte(a: char, b: u8) -> char {
         char::from_u32((b as i64 + a as i64) as u32).unwrap_or('\0')
     }
+    pub fn com_com(a: Complex, b: Complex) -> Complex {
+        b + a
+    }
+    pub fn com_num(a: Complex, b: f64) -> Complex {
+        com_com(a, b.into())
+    }
+    pub fn num_com(a: f64, b: Complex) -> Complex {
+        com_com(a.into(), b)
+    }
+    pub fn com_byte(a: Complex, b: u8) -> Complex {
+        com_com(a, b.into())
+    }
+    pub fn byte_com(a: u8, b: Complex) -> Complex {
+        com_com(a.into(), b)
+    }
+    pub fn rat_rat(a: Rational, b: Rational) -> Rational {
+        b + a
+    }
+    pub fn rat_byte(a: Rational, b: u8) -> Rational {
+        rat_rat(a, b.into())
+    }
+    pub fn byte_rat(a: u8, b: Rational) -> Rational {
+        rat_rat(a.into(), b)
+    }
+    pub fn rat_num(a: Rational, b: f64) -> f64 {
+        num_num(a.to_f64(), b)
+    }
+    pub fn num_rat(a: f64, b: Rational) -> f64 {
+        num_num(a, b.to_f64())
+    }
     pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot add {a} and {b}"))
     }
@@ -508,6 +690,36 @@ pub mod sub {
     pub fn byte_char(a: u8, b: char) -> char {
         char::from_u32(((b as i64) - (a as i64)) as u32).unwrap_or('\0')
     }
+    pub fn com_com(a: Complex, b: Complex) -> Complex {
+        b - a
+    }
+    pub fn com_num(a: Complex, b: f64) -> Complex {
+        com_com(a, b.into())
+    }
+    pub fn num_com(a: f64, b: Complex) -> Complex {
+        com_com(a.into(), b)
+    }
+    pub fn com_byte(a: Complex, b: u8) -> Complex {
+        com_com(a, b.into())
+    }
+    pub fn byte_com(a: u8, b: Complex) -> Complex {
+        com_com(a.into(), b)
+    }
+    pub fn rat_rat(a: Rational, b: Rational) -> Rational {
+        b - a
+    }
+    pub fn rat_byte(a: Rational, b: u8) -> Rational {
+        rat_rat(a, b.into())
+    }
+    pub fn byte_rat(a: u8, b: Rational) -> Rational {
+        rat_rat(a.into(), b)
+    }
+    pub fn rat_num(a: Rational, b: f64) -> f64 {
+        num_num(a.to_f64(), b)
+    }
+    pub fn num_rat(a: f64, b: Rational) -> f64 {
+        num_num(a, b.to_f64())
+    }
     pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot subtract {a} from {b}"))
     }
@@ -527,6 +739,36 @@ pub mod mul {
     pub fn num_byte(a: f64, b: u8) -> f64 {
         f64::from(b) * a
     }
+    pub fn com_com(a: Complex, b: Complex) -> Complex {
+        b * a
+    }
+    pub fn com_num(a: Complex, b: f64) -> Complex {
+        com_com(a, b.into())
+    }
+    pub fn num_com(a: f64, b: Complex) -> Complex {
+        com_com(a.into(), b)
+    }
+    pub fn com_byte(a: Complex, b: u8) -> Complex {
+        com_com(a, b.into())
+    }
+    pub fn byte_com(a: u8, b: Complex) -> Complex {
+        com_com(a.into(), b)
+    }
+    pub fn rat_rat(a: Rational, b: Rational) -> Rational {
+        b * a
+    }
+    pub fn rat_byte(a: Rational, b: u8) -> Rational {
+        rat_rat(a, b.into())
+    }
+    pub fn byte_rat(a: u8, b: Rational) -> Rational {
+        rat_rat(a.into(), b)
+    }
+    pub fn rat_num(a: Rational, b: f64) -> f64 {
+        num_num(a.to_f64(), b)
+    }
+    pub fn num_rat(a: f64, b: Rational) -> f64 {
+        num_num(a, b.to_f64())
+    }
     pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot multiply {a} and {b}"))
     }
@@ -546,6 +788,36 @@ pub mod div {
     pub fn num_byte(a: f64, b: u8) -> f64 {
         f64::from(b) / a
     }
+    pub fn com_com(a: Complex, b: Complex) -> Complex {
+        b / a
+    }
+    pub fn com_num(a: Complex, b: f64) -> Complex {
+        com_com(a, b.into())
+    }
+    pub fn num_com(a: f64, b: Complex) -> Complex {
+        com_com(a.into(), b)
+    }
+    pub fn com_byte(a: Complex, b: u8) -> Complex {
+        com_com(a, b.into())
+    }
+    pub fn byte_com(a: u8, b: Complex) -> Complex {
+        com_com(a.into(), b)
+    }
+    pub fn rat_rat(a: Rational, b: Rational) -> Rational {
+        b / a
+    }
+    pub fn rat_byte(a: Rational, b: u8) -> Rational {
+        rat_rat(a, b.into())
+    }
+    pub fn byte_rat(a: u8, b: Rational) -> Rational {
+        rat_rat(a.into(), b)
+    }
+    pub fn rat_num(a: Rational, b: f64) -> f64 {
+        num_num(a.to_f64(), b)
+    }
+    pub fn num_rat(a: f64, b: Rational) -> f64 {
+        num_num(a, b.to_f64())
+    }
     pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot divide {a} by {b}"))
     }
@@ -595,6 +867,21 @@ pub mod pow {
     pub fn num_byte(a: f64, b: u8) -> f64 {
         f64::from(b).powf(a)
     }
+    pub fn com_com(a: Complex, b: Complex) -> Complex {
+        b.powc(a)
+    }
+    pub fn com_num(a: Complex, b: f64) -> Complex {
+        com_com(a, b.into())
+    }
+    pub fn num_com(a: f64, b: Complex) -> Complex {
+        com_com(a.into(), b)
+    }
+    pub fn com_byte(a: Complex, b: u8) -> Complex {
+        com_com(a, b.into())
+    }
+    pub fn byte_com(a: u8, b: Complex) -> Complex {
+        com_com(a.into(), b)
+    }
     pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
         env.error(format!("Cannot get the power of {a} to {b}"))
     }
@@ -671,6 +958,312 @@ pub mod min {
     }
 }
 
+pub mod float {
+    use super::*;
+    pub fn num(a: f64) -> f64 {
+        a
+    }
+    pub fn byte(a: u8) -> u8 {
+        a
+    }
+    pub fn rat(a: Rational) -> f64 {
+        a.to_f64()
+    }
+    pub fn error<T: Display>(a: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot convert {a} to a float"))
+    }
+}
+
+pub mod rational {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> Rational {
+        Rational::new(a as i64, b as i64)
+    }
+    pub fn byte_byte(a: u8, b: u8) -> Rational {
+        Rational::new(a.into(), b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> Rational {
+        Rational::new(a.into(), b as i64)
+    }
+    pub fn num_byte(a: f64, b: u8) -> Rational {
+        Rational::new(a as i64, b.into())
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot make a rational number from {a} and {b}"))
+    }
+}
+
+pub mod complex {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> Complex {
+        Complex::new(a, b)
+    }
+    pub fn byte_byte(a: u8, b: u8) -> Complex {
+        Complex::new(a.into(), b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> Complex {
+        Complex::new(a.into(), b)
+    }
+    pub fn num_byte(a: f64, b: u8) -> Complex {
+        Complex::new(a, b.into())
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot make a complex number from {a} and {b}"))
+    }
+}
+
+pub mod gcd {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        let (mut a, mut b) = (a.round().abs(), b.round().abs());
+        while b != 0.0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    }
+    pub fn byte_byte(a: u8, b: u8) -> u8 {
+        num_num(a.into(), b.into()) as u8
+    }
+    pub fn num_byte(a: f64, b: u8) -> f64 {
+        num_num(a, b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the gcd of {a} and {b}"))
+    }
+}
+
+pub mod lcm {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        let (a, b) = (a.round().abs(), b.round().abs());
+        if a == 0.0 || b == 0.0 {
+            return 0.0;
+        }
+        // Divide before multiplying so that large inputs don't overflow early
+        a / gcd::num_num(a, b) * b
+    }
+    pub fn byte_byte(a: u8, b: u8) -> f64 {
+        num_num(a.into(), b.into())
+    }
+    pub fn num_byte(a: f64, b: u8) -> f64 {
+        num_num(a, b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the lcm of {a} and {b}"))
+    }
+}
+
+/// Round `x` to a multiple of `step` with a rounding function
+///
+/// The sign of `step` is ignored, and a `step` of `0` leaves `x` unchanged.
+fn to_multiple(step: f64, x: f64, round: fn(f64) -> f64) -> f64 {
+    if step == 0.0 {
+        return x;
+    }
+    let step = step.abs();
+    if step.is_infinite() {
+        // The only multiples are 0 and ±∞
+        if x == 0.0 || !x.is_finite() {
+            return x;
+        }
+        let quotient = round(x.signum() * f64::MIN_POSITIVE);
+        return if quotient == 0.0 {
+            0.0
+        } else {
+            quotient * f64::INFINITY
+        };
+    }
+    // Steps like 0.1 are not exactly representable, but their reciprocals are,
+    // and dividing by the reciprocal gives the closest float to the true multiple
+    let recip = 1.0 / step;
+    if recip.fract() == 0.0 {
+        round(x * recip) / recip
+    } else {
+        round(x / step) * step
+    }
+}
+
+pub mod nearest {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        to_multiple(a, b, f64::round)
+    }
+    pub fn byte_byte(a: u8, b: u8) -> f64 {
+        num_num(a.into(), b.into())
+    }
+    pub fn num_byte(a: f64, b: u8) -> f64 {
+        num_num(a, b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot round {b} to a multiple of {a}"))
+    }
+}
+
+pub mod below {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        to_multiple(a, b, f64::floor)
+    }
+    pub fn byte_byte(a: u8, b: u8) -> f64 {
+        num_num(a.into(), b.into())
+    }
+    pub fn num_byte(a: f64, b: u8) -> f64 {
+        num_num(a, b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot floor {b} to a multiple of {a}"))
+    }
+}
+
+pub mod above {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        to_multiple(a, b, f64::ceil)
+    }
+    pub fn byte_byte(a: u8, b: u8) -> f64 {
+        num_num(a.into(), b.into())
+    }
+    pub fn num_byte(a: f64, b: u8) -> f64 {
+        num_num(a, b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot ceil {b} to a multiple of {a}"))
+    }
+}
+
+pub mod nanfill {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        if b.is_nan() {
+            a
+        } else {
+            b
+        }
+    }
+    pub fn byte_byte(_: u8, b: u8) -> u8 {
+        b
+    }
+    pub fn num_byte(_: f64, b: u8) -> f64 {
+        b.into()
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot fill NaNs in {b} with {a}"))
+    }
+}
+
+pub mod and {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        (a != 0.0 && b != 0.0) as u8 as f64
+    }
+    pub fn byte_byte(a: u8, b: u8) -> u8 {
+        (a != 0 && b != 0) as u8
+    }
+    pub fn num_byte(a: f64, b: u8) -> f64 {
+        num_num(a, b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the logical and of {a} and {b}"))
+    }
+}
+
+pub mod or {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        (a != 0.0 || b != 0.0) as u8 as f64
+    }
+    pub fn byte_byte(a: u8, b: u8) -> u8 {
+        (a != 0 || b != 0) as u8
+    }
+    pub fn num_byte(a: f64, b: u8) -> f64 {
+        num_num(a, b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the logical or of {a} and {b}"))
+    }
+}
+
+pub mod bitand {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        (a.round() as i64 & b.round() as i64) as f64
+    }
+    pub fn byte_byte(a: u8, b: u8) -> u8 {
+        a & b
+    }
+    pub fn num_byte(a: f64, b: u8) -> f64 {
+        num_num(a, b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the bitwise and of {a} and {b}"))
+    }
+}
+
+pub mod bitor {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        (a.round() as i64 | b.round() as i64) as f64
+    }
+    pub fn byte_byte(a: u8, b: u8) -> u8 {
+        a | b
+    }
+    pub fn num_byte(a: f64, b: u8) -> f64 {
+        num_num(a, b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the bitwise or of {a} and {b}"))
+    }
+}
+
+pub mod bitxor {
+    use super::*;
+    pub fn num_num(a: f64, b: f64) -> f64 {
+        (a.round() as i64 ^ b.round() as i64) as f64
+    }
+    pub fn byte_byte(a: u8, b: u8) -> u8 {
+        a ^ b
+    }
+    pub fn num_byte(a: f64, b: u8) -> f64 {
+        num_num(a, b.into())
+    }
+    pub fn byte_num(a: u8, b: f64) -> f64 {
+        num_num(a.into(), b)
+    }
+    pub fn error<T: Display>(a: T, b: T, env: &Uiua) -> UiuaError {
+        env.error(format!("Cannot get the bitwise xor of {a} and {b}"))
+    }
+}
+
 pub trait PervasiveInput: IntoIterator + Sized {
     type OwnedItem: Clone;
     fn len(&self) -> usize;
@@ -8,9 +8,11 @@ use std::{
 use tinyvec::{tiny_vec, TinyVec};
 
 use crate::{
+    complex::Complex,
     cowslice::{cowslice, CowSlice},
     function::Function,
     grid_fmt::GridFmt,
+    rational::Rational,
     value::Value,
     Uiua,
 };
@@ -289,7 +291,9 @@ impl<T: ArrayValue + ArrayCmp<U>, U: ArrayValue> PartialOrd<Array<U>> for Array<
             .zip(&other.data)
             .map(|(a, b)| a.array_cmp(b))
             .find(|o| o != &Ordering::Equal)
-            .unwrap_or_else(|| self.data.len().cmp(&other.data.len()));
+            .unwrap_or_else(|| {
+                (self.data.len().cmp(&other.data.len())).then_with(|| self.shape.cmp(&other.shape))
+            });
         Some(cmp)
     }
 }
@@ -365,8 +369,12 @@ impl FromIterator<String> for Array<Arc<Function>> {
 }
 
 #[allow(unused_variables)]
-pub trait ArrayValue: Clone + Debug + Display + GridFmt + ArrayCmp {
+pub trait ArrayValue: Clone + Debug + Display + GridFmt + ArrayCmp + Send + Sync {
     const NAME: &'static str;
+    /// Where values of this type go when values of different types are ordered
+    ///
+    /// Types with the same order are compared by value.
+    const TYPE_ORDER: u8;
     fn get_fill(env: &Uiua) -> Option<Self>;
     fn array_hash<H: Hasher>(&self, hasher: &mut H);
     fn format_delims() -> (&'static str, &'static str) {
@@ -382,6 +390,7 @@ pub trait ArrayValue: Clone + Debug + Display + GridFmt + ArrayCmp {
 
 impl ArrayValue for f64 {
     const NAME: &'static str = "number";
+    const TYPE_ORDER: u8 = 0;
     fn get_fill(env: &Uiua) -> Option<Self> {
         env.num_fill()
     }
@@ -399,6 +408,7 @@ impl ArrayValue for f64 {
 
 impl ArrayValue for u8 {
     const NAME: &'static str = "number";
+    const TYPE_ORDER: u8 = 0;
     fn get_fill(env: &Uiua) -> Option<Self> {
         env.byte_fill()
     }
@@ -409,6 +419,7 @@ impl ArrayValue for u8 {
 
 impl ArrayValue for char {
     const NAME: &'static str = "character";
+    const TYPE_ORDER: u8 = 2;
     fn get_fill(env: &Uiua) -> Option<Self> {
         env.char_fill()
     }
@@ -423,8 +434,34 @@ impl ArrayValue for char {
     }
 }
 
+impl ArrayValue for Complex {
+    const NAME: &'static str = "complex";
+    const TYPE_ORDER: u8 = 1;
+    fn get_fill(env: &Uiua) -> Option<Self> {
+        env.num_fill().map(Complex::from)
+    }
+    fn array_hash<H: Hasher>(&self, hasher: &mut H) {
+        self.re.array_hash(hasher);
+        self.im.array_hash(hasher);
+    }
+}
+
+impl ArrayValue for Rational {
+    const NAME: &'static str = "rational";
+    const TYPE_ORDER: u8 = 0;
+    fn get_fill(env: &Uiua) -> Option<Self> {
+        (env.num_fill())
+            .filter(|n| n.fract() == 0.0 && n.abs() <= i64::MAX as f64)
+            .map(|n| Rational::from(n as i64))
+    }
+    fn array_hash<H: Hasher>(&self, hasher: &mut H) {
+        self.hash(hasher)
+    }
+}
+
 impl ArrayValue for Arc<Function> {
     const NAME: &'static str = "function";
+    const TYPE_ORDER: u8 = 3;
     fn get_fill(env: &Uiua) -> Option<Self> {
         env.func_fill()
     }
@@ -477,6 +514,20 @@ impl ArrayCmp for char {
     }
 }
 
+impl ArrayCmp for Complex {
+    fn array_cmp(&self, other: &Self) -> Ordering {
+        self.re
+            .array_cmp(&other.re)
+            .then_with(|| self.im.array_cmp(&other.im))
+    }
+}
+
+impl ArrayCmp for Rational {
+    fn array_cmp(&self, other: &Self) -> Ordering {
+        self.cmp(other)
+    }
+}
+
 impl ArrayCmp for Arc<Function> {
     fn array_cmp(&self, other: &Self) -> Ordering {
         self.cmp(other)
@@ -495,6 +546,64 @@ impl ArrayCmp<u8> for f64 {
     }
 }
 
+/// Rationals compare with other real numbers by value,
+/// and are greater than a number they are indistinguishable from as a float
+impl ArrayCmp<f64> for Rational {
+    fn array_cmp(&self, other: &f64) -> Ordering {
+        (self.to_f64().array_cmp(other)).then(Ordering::Greater)
+    }
+}
+
+impl ArrayCmp<Rational> for f64 {
+    fn array_cmp(&self, other: &Rational) -> Ordering {
+        other.array_cmp(self).reverse()
+    }
+}
+
+impl ArrayCmp<u8> for Rational {
+    fn array_cmp(&self, other: &u8) -> Ordering {
+        self.array_cmp(&(*other as f64))
+    }
+}
+
+impl ArrayCmp<Rational> for u8 {
+    fn array_cmp(&self, other: &Rational) -> Ordering {
+        other.array_cmp(self).reverse()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Array<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("Array", 2)?;
+        state.serialize_field("shape", self.shape.as_slice())?;
+        state.serialize_field("data", &*self.data)?;
+        state.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de> + Clone> serde::Deserialize<'de> for Array<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Array")]
+        struct Rep<T> {
+            shape: Vec<usize>,
+            data: Vec<T>,
+        }
+        let Rep { shape, data } = Rep::deserialize(deserializer)?;
+        if shape.iter().product::<usize>() != data.len() {
+            return Err(serde::de::Error::custom(format!(
+                "shape {} does not match data length {}",
+                FormatShape(&shape),
+                data.len()
+            )));
+        }
+        Ok(Array::new(Shape::from(shape.as_slice()), data))
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct FormatShape<'a>(pub &'a [usize]);
 
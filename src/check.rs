@@ -78,6 +78,8 @@ impl<'a> BasicValue<'a> {
                 Value::Num(n) => n.data.iter().map(|n| BasicValue::Num(*n)).collect(),
                 Value::Byte(b) => b.data.iter().map(|b| BasicValue::Num(*b as f64)).collect(),
                 Value::Char(c) => c.data.iter().map(|_| BasicValue::Other).collect(),
+                Value::Complex(c) => c.data.iter().map(|_| BasicValue::Other).collect(),
+                Value::Rational(c) => c.data.iter().map(|_| BasicValue::Other).collect(),
                 Value::Func(f) => f
                     .data
                     .iter()
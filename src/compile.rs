@@ -5,7 +5,7 @@ use std::{
 };
 
 use crate::{
-    algorithm::invert::under_instrs,
+    algorithm::{invert::under_instrs, loops::reduce_range},
     array::Array,
     ast::*,
     check::instrs_signature,
@@ -45,7 +45,13 @@ impl Uiua {
                 };
                 if can_run || words_have_import(&words) {
                     let instrs = self.compile_words(words, true)?;
-                    self.exec_global_instrs(instrs)?;
+                    match self.exec_global_instrs(instrs) {
+                        // In test mode, failed assertions are collected instead of stopping
+                        Err(e) if in_test && self.mode == RunMode::Test && e.is_throw() => {
+                            self.test_results.failures.push(e);
+                        }
+                        res => res?,
+                    }
                 }
             }
             Item::Binding(binding) => {
@@ -187,6 +193,30 @@ impl Uiua {
             }
             // First reverse = last
             ([.., Instr::Prim(top @ Reverse, _)], Instr::Prim(First, _)) => *top = Last,
+            // Reduce range without building the range
+            ([.., Instr::Prim(Range, range_span), Instr::Push(f)], Instr::Prim(Reduce, span))
+                if matches!(f.as_primitive(), Some((Add | Mul | Max | Min, _))) =>
+            {
+                let f = Value::clone(f);
+                let (prim, _) = f.as_primitive().unwrap();
+                let range_span = *range_span;
+                instrs.pop();
+                instrs.pop();
+                instrs.push(Instr::Dynamic(DynamicFunction {
+                    id: {
+                        let mut hasher = DefaultHasher::new();
+                        (Reduce, prim, Range).hash(&mut hasher);
+                        hasher.finish()
+                    },
+                    f: Arc::new(move |env| {
+                        env.push_span(span, Some(Reduce));
+                        reduce_range(f.clone(), range_span, env)?;
+                        env.pop_span();
+                        Ok(())
+                    }),
+                    signature: Signature::new(1, 1),
+                }));
+            }
             (_, instr) => instrs.push(instr),
         }
     }
@@ -0,0 +1,169 @@
+//! Complex numbers
+
+use std::{
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// Uiua's complex number type
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Complex {
+    /// The real part
+    pub re: f64,
+    /// The imaginary part
+    pub im: f64,
+}
+
+impl Complex {
+    /// The number `0`
+    pub const ZERO: Self = Self::new(0.0, 0.0);
+    /// The number `1`
+    pub const ONE: Self = Self::new(1.0, 0.0);
+    /// The imaginary unit
+    pub const I: Self = Self::new(0.0, 1.0);
+    pub const fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+    /// Make a complex number from its magnitude and angle
+    pub fn from_polar(r: f64, theta: f64) -> Self {
+        Self::new(r * theta.cos(), r * theta.sin())
+    }
+    /// The magnitude of the number
+    pub fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+    /// The angle of the number in radians
+    pub fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+    /// The principal square root
+    pub fn sqrt(self) -> Self {
+        let r = self.abs();
+        let re = ((r + self.re) / 2.0).sqrt();
+        let im = ((r - self.re) / 2.0).sqrt();
+        Self::new(re, if self.im < 0.0 { -im } else { im })
+    }
+    /// The number with its magnitude normalized to `1`
+    ///
+    /// Zero stays zero.
+    pub fn normalize(self) -> Self {
+        let r = self.abs();
+        if r == 0.0 {
+            self
+        } else {
+            self / r
+        }
+    }
+    /// The natural logarithm
+    pub fn ln(self) -> Self {
+        Self::new(self.abs().ln(), self.arg())
+    }
+    /// `e` raised to the power of the number
+    pub fn exp(self) -> Self {
+        Self::from_polar(self.re.exp(), self.im)
+    }
+    /// Raise the number to a complex power
+    pub fn powc(self, power: Self) -> Self {
+        if power == Self::ZERO {
+            Self::ONE
+        } else if self == Self::ZERO {
+            Self::ZERO
+        } else {
+            (self.ln() * power).exp()
+        }
+    }
+}
+
+impl From<f64> for Complex {
+    fn from(re: f64) -> Self {
+        Self::new(re, 0.0)
+    }
+}
+
+impl From<u8> for Complex {
+    fn from(re: u8) -> Self {
+        Self::new(re.into(), 0.0)
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let denom = rhs.re * rhs.re + rhs.im * rhs.im;
+        Self::new(
+            (self.re * rhs.re + self.im * rhs.im) / denom,
+            (self.im * rhs.re - self.re * rhs.im) / denom,
+        )
+    }
+}
+
+impl Mul<f64> for Complex {
+    type Output = Self;
+    fn mul(self, rhs: f64) -> Self {
+        Self::new(self.re * rhs, self.im * rhs)
+    }
+}
+
+impl Div<f64> for Complex {
+    type Output = Self;
+    fn div(self, rhs: f64) -> Self {
+        Self::new(self.re / rhs, self.im / rhs)
+    }
+}
+
+impl Neg for Complex {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.im == 0.0 {
+            write!(f, "{}", self.re)
+        } else if self.re == 0.0 {
+            write!(f, "{}i", self.im)
+        } else if self.im < 0.0 {
+            write!(f, "{}-{}i", self.re, -self.im)
+        } else {
+            write!(f, "{}+{}i", self.re, self.im)
+        }
+    }
+}
+
+#[test]
+fn complex_arithmetic() {
+    let a = Complex::new(1.0, 2.0);
+    let b = Complex::new(3.0, -1.0);
+    assert_eq!(a + b, Complex::new(4.0, 1.0));
+    assert_eq!(a * b, Complex::new(5.0, 5.0));
+    assert_eq!((a * b) / b, a);
+    assert_eq!(Complex::from(-1.0).sqrt(), Complex::I);
+    assert_eq!(Complex::new(3.0, -4.0).sqrt(), Complex::new(2.0, -1.0));
+}
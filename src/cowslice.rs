@@ -73,6 +73,10 @@ impl<T: Clone> CowSlice<T> {
             res
         }
     }
+    /// Reserve capacity for at least `additional` more elements
+    pub fn reserve(&mut self, additional: usize) {
+        self.modify(|vec| vec.reserve(additional))
+    }
 }
 
 #[test]
@@ -87,6 +91,17 @@ fn cow_slice_modify() {
     assert_eq!(sub, [2, 3, 5]);
 }
 
+#[test]
+fn cow_slice_shares_until_modified() {
+    let a = CowSlice::from([1, 2, 3]);
+    let mut b = a.clone();
+    assert_eq!(a.as_ptr(), b.as_ptr());
+    b.modify(|vec| vec.make_mut().reverse());
+    assert_ne!(a.as_ptr(), b.as_ptr());
+    assert_eq!(a, [1, 2, 3]);
+    assert_eq!(b, [3, 2, 1]);
+}
+
 impl<T> Default for CowSlice<T> {
     fn default() -> Self {
         Self {
@@ -30,11 +30,33 @@ pub enum UiuaError {
     Throw(Box<Value>, Span),
     Break(usize, Span),
     Timeout(Span),
+    Cancelled(Span),
     Fill(Box<Self>),
 }
 
 pub type UiuaResult<T = ()> = Result<T, UiuaError>;
 
+/// The kind of a [`UiuaError`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UiuaErrorKind {
+    /// A file could not be loaded
+    Load,
+    /// A file could not be formatted
+    Format,
+    /// The code could not be parsed
+    Parse,
+    /// An error occurred while running the code
+    Run,
+    /// A value was thrown with `assert`
+    Throw,
+    /// A `break` escaped its loop
+    Break,
+    /// The execution limit was exceeded
+    Timeout,
+    /// Execution was cancelled
+    Cancelled,
+}
+
 impl From<Sp<String, Span>> for UiuaError {
     fn from(value: Sp<String, Span>) -> Self {
         Self::Run(value)
@@ -76,6 +98,7 @@ impl fmt::Display for UiuaError {
             UiuaError::Throw(value, span) => write!(f, "{span}: {value}"),
             UiuaError::Break(_, span) => write!(f, "{span}: break outside of loop"),
             UiuaError::Timeout(_) => write!(f, "Maximum execution time exceeded"),
+            UiuaError::Cancelled(_) => write!(f, "Execution was cancelled"),
             UiuaError::Fill(error) => error.fmt(f),
         }
     }
@@ -107,6 +130,40 @@ impl UiuaError {
             error => Err(error),
         }
     }
+    /// Get the kind of the error, looking through traces
+    pub fn kind(&self) -> UiuaErrorKind {
+        match self {
+            UiuaError::Load(..) => UiuaErrorKind::Load,
+            UiuaError::Format(..) => UiuaErrorKind::Format,
+            UiuaError::Parse(_) => UiuaErrorKind::Parse,
+            UiuaError::Run(_) => UiuaErrorKind::Run,
+            UiuaError::Traced { error, .. } | UiuaError::Fill(error) => error.kind(),
+            UiuaError::Throw(..) => UiuaErrorKind::Throw,
+            UiuaError::Break(..) => UiuaErrorKind::Break,
+            UiuaError::Timeout(_) => UiuaErrorKind::Timeout,
+            UiuaError::Cancelled(_) => UiuaErrorKind::Cancelled,
+        }
+    }
+    /// Get the span of the code that caused the error
+    ///
+    /// For parse errors, this is the span of the first error.
+    /// Errors that do not come from code, like failing to load a file, have no span.
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            UiuaError::Load(..) | UiuaError::Format(..) => None,
+            UiuaError::Parse(errors) => errors.first().map(|error| error.span.clone().into()),
+            UiuaError::Run(error) => Some(error.span.clone()),
+            UiuaError::Traced { error, .. } | UiuaError::Fill(error) => error.span(),
+            UiuaError::Throw(_, span)
+            | UiuaError::Break(_, span)
+            | UiuaError::Timeout(span)
+            | UiuaError::Cancelled(span) => Some(span.clone()),
+        }
+    }
+    /// Check if the error was thrown by `assert`
+    pub(crate) fn is_throw(&self) -> bool {
+        self.kind() == UiuaErrorKind::Throw
+    }
     /// Check if the error is fill-related
     pub(crate) fn is_fill(&self) -> bool {
         match self {
@@ -213,6 +270,9 @@ impl UiuaError {
                 kind,
                 color,
             ),
+            UiuaError::Cancelled(span) => {
+                report([("Execution was cancelled", span.clone())], kind, color)
+            }
             UiuaError::Fill(error) => error.show(color),
             UiuaError::Load(..) | UiuaError::Format(..) => self.to_string(),
         }
@@ -285,6 +345,10 @@ where
                 input: Source::from(&span.input),
                 files: HashMap::new(),
             });
+            // Use the source the span was parsed from, which may not be on disk
+            if span.path.is_some() {
+                (cache.files.entry(span.path.clone())).or_insert_with(|| Source::from(&span.input));
+            }
             let report = Report::<CodeSpan>::build(kind, span.path.clone(), span.start.char_pos)
                 .with_message(message)
                 .with_label(Label::new(span.clone()).with_color(color))
@@ -362,3 +426,43 @@ impl ariadne::Cache<SourceId> for Cache {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Uiua;
+
+    #[test]
+    fn error_span() {
+        let mut env = Uiua::with_native_sys();
+        let error = env.load_str("1\n+ 2\n\n+").unwrap_err();
+        assert_eq!(error.kind(), UiuaErrorKind::Run);
+        let Some(Span::Code(span)) = error.span() else {
+            panic!("error has no code span: {error}");
+        };
+        assert_eq!((span.start.line, span.start.col), (4, 1));
+        let error = env.load_str("(").unwrap_err();
+        assert_eq!(error.kind(), UiuaErrorKind::Parse);
+        assert!(error.span().is_some());
+    }
+
+    #[test]
+    fn error_rendering() {
+        let mut env = Uiua::with_native_sys();
+        let error = env.load_str("x ← 1\n[1 2 3 ) 4").unwrap_err();
+        let shown = error.show(false);
+        let lines: Vec<&str> = shown.lines().collect();
+        let source_index = (lines.iter())
+            .position(|line| line.ends_with("[1 2 3 ) 4"))
+            .unwrap_or_else(|| panic!("source line not shown:\n{shown}"));
+        let source_line = lines[source_index];
+        let code_start = source_line.chars().count() - "[1 2 3 ) 4".chars().count();
+        // The span of the error is underlined
+        let underline_col = lines[source_index + 1]
+            .chars()
+            .skip(code_start)
+            .position(|c| c == '─')
+            .unwrap_or_else(|| panic!("span not underlined:\n{shown}"));
+        assert_eq!(underline_col, 6);
+    }
+}
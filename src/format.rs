@@ -6,6 +6,7 @@ use std::{
     env,
     fmt::Display,
     fs,
+    ops::Range,
     path::{Path, PathBuf},
 };
 
@@ -16,7 +17,9 @@ use crate::{
     function::Signature,
     grid_fmt::GridFmt,
     lex::{CodeSpan, Loc, Sp},
+    lsp::{spans, SpanKind},
     parse::parse,
+    primitive::Primitive,
     value::Value,
     SysBackend, Uiua, UiuaError, UiuaResult,
 };
@@ -198,6 +201,8 @@ create_config!(
     ),
     /// The number of characters on line preceding a multiline array or function, at or before which the multiline will be compact.
     (multiline_compact_threshold, usize, 10),
+    /// Whether to insert a space after monadic function glyphs.
+    (space_after_monadic_glyphs, bool, false),
 );
 
 /// The source from which to populate the formatter configuration.
@@ -337,11 +342,26 @@ fn format_impl(
     config: &FormatConfig,
 ) -> UiuaResult<FormatOutput> {
     let (items, errors, _) = parse(input, path);
-    if errors.is_empty() {
-        Ok(format_items(&items, config))
-    } else {
-        Err(errors.into())
+    if !errors.is_empty() {
+        return Err(errors.into());
     }
+    let formatted = format_items(&items, config);
+    // Formatted code should parse, and formatting it again should not change it
+    #[cfg(test)]
+    {
+        let (items, errors, _) = parse(&formatted.output, path);
+        assert!(
+            errors.is_empty(),
+            "Formatted code does not parse: {errors:?}\n{}",
+            formatted.output
+        );
+        assert_eq!(
+            format_items(&items, config).output,
+            formatted.output,
+            "Formatting is not idempotent"
+        );
+    }
+    Ok(formatted)
 }
 
 pub fn format_file<P: AsRef<Path>>(path: P, config: &FormatConfig) -> UiuaResult<FormatOutput> {
@@ -360,6 +380,230 @@ pub fn format_file<P: AsRef<Path>>(path: P, config: &FormatConfig) -> UiuaResult
     Ok(formatted)
 }
 
+/// Check if a file is already formatted without writing to it
+pub fn check_file<P: AsRef<Path>>(path: P, config: &FormatConfig) -> UiuaResult<bool> {
+    let path = path.as_ref();
+    let input =
+        fs::read_to_string(path).map_err(|e| UiuaError::Load(path.to_path_buf(), e.into()))?;
+    let formatted = format(&input, path, config)?;
+    Ok(formatted.output == input)
+}
+
+/// Find the primitive that a name or name prefix formats to
+///
+/// Returns `None` if the prefix is too short or matches more than one primitive.
+/// A few prefixes that matched a single primitive before newer primitives were added
+/// still resolve to that primitive.
+pub fn prefix_match(input: &str) -> Option<Primitive> {
+    Primitive::from_format_name(input)
+}
+
+/// A span of code and its category, for syntax highlighting
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    /// The byte range of the token in the source
+    pub range: Range<usize>,
+    /// What kind of syntax the token is
+    pub kind: SpanKind,
+}
+
+/// Split code into highlightable tokens
+///
+/// Tokens are ordered by position. Whitespace is not included.
+pub fn tokens(input: &str) -> Vec<Token> {
+    let mut tokens: Vec<Token> = (spans(input).into_iter())
+        .filter(|span| span.value != SpanKind::Whitespace)
+        .map(|span| Token {
+            range: span.span.start.byte_pos..span.span.end.byte_pos,
+            kind: span.value,
+        })
+        .collect();
+    tokens.sort_by_key(|token| token.range.start);
+    tokens
+}
+
+#[test]
+fn tokens_test() {
+    let input = "⇌ +1 \"hi\" 2_3 # comment";
+    let tokens: Vec<(&str, SpanKind)> = (tokens(input).into_iter())
+        .map(|token| (&input[token.range], token.kind))
+        .collect();
+    assert_eq!(
+        tokens,
+        [
+            ("⇌", SpanKind::Primitive(Primitive::Reverse)),
+            ("+", SpanKind::Primitive(Primitive::Add)),
+            ("1", SpanKind::Number),
+            ("\"hi\"", SpanKind::String),
+            ("2", SpanKind::Number),
+            ("_", SpanKind::Strand),
+            ("3", SpanKind::Number),
+            ("# comment", SpanKind::Comment),
+        ]
+    );
+}
+
+#[test]
+fn prefix_match_test() {
+    assert_eq!(prefix_match("cei"), Some(Primitive::Ceil));
+    assert_eq!(prefix_match("ceil"), Some(Primitive::Ceil));
+    assert_eq!(prefix_match("c"), None);
+    assert_eq!(prefix_match("co"), None);
+    assert_eq!(prefix_match("ind"), Some(Primitive::IndexOf));
+    assert_eq!(prefix_match("indi"), Some(Primitive::Indices));
+    assert_eq!(prefix_match("dis"), Some(Primitive::Distribute));
+    assert_eq!(prefix_match("sor"), Some(Primitive::Sort));
+    assert_eq!(prefix_match("pars"), Some(Primitive::Parse));
+    assert_eq!(prefix_match("xyz"), None);
+}
+
+#[test]
+fn format_str_test() {
+    let output = format_str("%6 *3 8", &FormatConfig::default())
+        .unwrap()
+        .output;
+    assert_eq!(output, "÷6 ×3 8\n");
+}
+
+#[test]
+fn format_strands() {
+    let config = FormatConfig::default();
+    for (input, expected) in [
+        ("1_`2_3", "1_¯2_3\n"),
+        ("1_ 2", "1_2\n"),
+        ("1.50_2", "1.5_2\n"),
+        ("@a_@b", "@a_@b\n"),
+        ("1_", "1_\n"),
+    ] {
+        assert_eq!(format_str(input, &config).unwrap().output, expected);
+    }
+}
+
+#[test]
+fn format_bindings() {
+    let config = FormatConfig::default();
+    for (input, expected) in [
+        ("Double = mul2", "Double ← ×2\n"),
+        ("Double ← ×2", "Double ← ×2\n"),
+        ("Rev = rev", "Rev ← ⇌\n"),
+        ("xs = [1 2]\nlen xs", "xs ← [1 2]\n⧻ xs\n"),
+    ] {
+        assert_eq!(format_str(input, &config).unwrap().output, expected);
+    }
+}
+
+#[test]
+fn format_ambiguous_prefixes() {
+    let config = FormatConfig::default();
+    for (input, expected) in [
+        ("dis+ 1 [2 3]", "∺+ 1 [2 3]\n"),
+        ("dist+ 1 [2 3]", "∺+ 1 [2 3]\n"),
+        ("dista 1 3", "distance 1 3\n"),
+        ("ind [1 2] 2", "⊗ [1 2] 2\n"),
+        ("indi 2_3", "indices 2_3\n"),
+        ("ass 1 1", "⍤ 1 1\n"),
+        ("asser 1 1", "⍤ 1 1\n"),
+        ("asserts 2 [1 2]", "assertshape 2 [1 2]\n"),
+        ("flo 1.5", "⌊ 1.5\n"),
+        ("floa 1", "float 1\n"),
+        ("bit 5", "⋯ 5\n"),
+        ("bitn 5", "bitnot 5\n"),
+        ("pars \"5\"", "parse \"5\"\n"),
+        ("parsej \"5\"", "parsejson \"5\"\n"),
+        ("cla [1 2]", "⊛ [1 2]\n"),
+        ("clam 0 1 2", "clamp 0 1 2\n"),
+        ("div 1 2", "÷ 1 2\n"),
+        ("divm 1 2", "divmod 1 2\n"),
+    ] {
+        assert_eq!(format_str(input, &config).unwrap().output, expected);
+    }
+}
+
+#[test]
+fn format_match_alias() {
+    let output = format_str("≅ [1 2] [1 2]", &FormatConfig::default())
+        .unwrap()
+        .output;
+    assert_eq!(output, "≍ [1 2] [1 2]\n");
+}
+
+#[test]
+fn format_space_after_monadic_glyphs() {
+    let input = "rev+1 2 (neg)rev [rev1_2] rev rev";
+    let default = FormatConfig::default();
+    assert_eq!(
+        format_str(input, &default).unwrap().output,
+        "⇌+1 2 (¯)⇌ [⇌1_2] ⇌ ⇌\n"
+    );
+    let spaced = FormatConfig::default().with_space_after_monadic_glyphs(true);
+    assert_eq!(
+        format_str(input, &spaced).unwrap().output,
+        "⇌ +1 2 (¯)⇌ [⇌ 1_2] ⇌ ⇌\n"
+    );
+    let no_newline = spaced.with_trailing_newline(false);
+    assert_eq!(format_str("revrev", &no_newline).unwrap().output, "⇌ ⇌");
+}
+
+#[test]
+fn format_preserves_comments() {
+    let input = "\
+# Leading comment
+
+x ← 5 # inline
+
+
+# Between
++1 x # Click Run to format!
+F ← (
+  +1 # add one
+  # alone
+  ×2
+)
+# Trailing comment";
+    let output = format_str(input, &FormatConfig::default()).unwrap().output;
+    // Runs of blank lines are collapsed to one
+    let expected = input.replacen("\n\n\n", "\n\n", 1) + "\n";
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn format_idempotent() {
+    let config = FormatConfig::default();
+    let mut inputs = vec![
+        "revrev tabkee 1_2_3".to_string(),
+        "%6 *3 8 (+1) !=1 2".to_string(),
+        "F ← |2 (\n  +\n)\n".to_string(),
+    ];
+    for dir in ["examples", "tests"] {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_some_and(|ext| ext == "ua") {
+                inputs.push(fs::read_to_string(path).unwrap());
+            }
+        }
+    }
+    for input in inputs {
+        let once = format_str(&input, &config).unwrap().output;
+        let twice = format_str(&once, &config).unwrap().output;
+        assert_eq!(once, twice, "Formatting is not idempotent for:\n{input}");
+    }
+}
+
+#[test]
+fn check_file_test() {
+    let dir = env::temp_dir().join("uiua_check_file_test");
+    fs::create_dir_all(&dir).unwrap();
+    let config = FormatConfig::default();
+    let formatted = dir.join("formatted.ua");
+    fs::write(&formatted, "÷6 ×3 8\n").unwrap();
+    assert!(check_file(&formatted, &config).unwrap());
+    let unformatted = dir.join("unformatted.ua");
+    fs::write(&unformatted, "%6 *3 8").unwrap();
+    assert!(!check_file(&unformatted, &config).unwrap());
+    assert_eq!(fs::read_to_string(&unformatted).unwrap(), "%6 *3 8");
+    fs::remove_dir_all(dir).unwrap();
+}
+
 struct Formatter<'a> {
     config: &'a FormatConfig,
     output: String,
@@ -408,8 +652,15 @@ impl<'a> Formatter<'a> {
         }
     }
     fn format_words(&mut self, words: &[Sp<Word>], trim_end: bool, depth: usize) {
-        for word in trim_spaces(words, trim_end) {
+        let words = trim_spaces(words, trim_end);
+        for (i, word) in words.iter().enumerate() {
             self.format_word(word, depth);
+            if self.config.space_after_monadic_glyphs
+                && word_is_monadic_glyph(&word.value)
+                && (words.get(i + 1)).is_some_and(|next| !matches!(next.value, Word::Spaces))
+            {
+                self.output.push(' ');
+            }
         }
     }
     fn format_word(&mut self, word: &Sp<Word>, depth: usize) {
@@ -609,6 +860,15 @@ fn trim_spaces(words: &[Sp<Word>], trim_end: bool) -> &[Sp<Word>] {
     &words[start..end]
 }
 
+fn word_is_monadic_glyph(word: &Word) -> bool {
+    match word {
+        Word::Primitive(prim) => {
+            prim.args() == Some(1) && !prim.is_modifier() && prim.unicode().is_some()
+        }
+        _ => false,
+    }
+}
+
 fn word_is_multiline(word: &Word) -> bool {
     match word {
         Word::Number(_, _) => false,
@@ -50,6 +50,7 @@ impl PartialEq for Instr {
             (Self::EndArray { .. }, Self::EndArray { .. }) => true,
             (Self::Prim(a, s_span), Self::Prim(b, b_span)) => a == b && s_span == b_span,
             (Self::Call(a), Self::Call(b)) => a == b,
+            (Self::Dynamic(a), Self::Dynamic(b)) => a == b,
             (Self::PushTemp { count: a, .. }, Self::PushTemp { count: b, .. }) => a == b,
             (Self::PopTemp { count: a, .. }, Self::PopTemp { count: b, .. }) => a == b,
             (
@@ -80,13 +81,30 @@ impl PartialOrd for Instr {
 
 impl Ord for Instr {
     fn cmp(&self, other: &Self) -> Ordering {
-        if self == other {
-            Ordering::Equal
-        } else {
-            let a: u8 = unsafe { transmute(discriminant(self)) };
-            let b: u8 = unsafe { transmute(discriminant(other)) };
-            a.cmp(&b)
-        }
+        let a: u8 = unsafe { transmute(discriminant(self)) };
+        let b: u8 = unsafe { transmute(discriminant(other)) };
+        a.cmp(&b).then_with(|| match (self, other) {
+            (Self::Push(a), Self::Push(b)) => a.cmp(b),
+            (Self::Prim(a, a_span), Self::Prim(b, b_span)) => (a, a_span).cmp(&(b, b_span)),
+            (Self::Call(a), Self::Call(b)) => a.cmp(b),
+            (Self::Dynamic(a), Self::Dynamic(b)) => a.cmp(b),
+            (Self::PushTemp { count: a, .. }, Self::PushTemp { count: b, .. })
+            | (Self::PopTemp { count: a, .. }, Self::PopTemp { count: b, .. })
+            | (Self::DropTemp { count: a, .. }, Self::DropTemp { count: b, .. }) => a.cmp(b),
+            (
+                Self::CopyTemp {
+                    offset: ao,
+                    count: ac,
+                    ..
+                },
+                Self::CopyTemp {
+                    offset: bo,
+                    count: bc,
+                    ..
+                },
+            ) => (ao, ac).cmp(&(bo, bc)),
+            _ => Ordering::Equal,
+        })
     }
 }
 
@@ -466,3 +484,29 @@ impl fmt::Display for FunctionId {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn function_literal_signatures() {
+        let signature = |code: &str| {
+            let mut env = Uiua::with_native_sys();
+            env.load_str(code).unwrap();
+            let value = env.pop(1).unwrap();
+            value.as_func_array().unwrap().data[0].signature()
+        };
+        assert_eq!(signature("(+1)"), Signature::new(1, 1));
+        assert_eq!(signature("(∵(+1))"), Signature::new(1, 1));
+        assert_eq!(
+            signature("(+)").args,
+            Primitive::Add.args().unwrap() as usize
+        );
+        assert_eq!(
+            signature("(⇌)").args,
+            Primitive::Reverse.args().unwrap() as usize
+        );
+        assert_eq!(signature("(+×)"), Signature::new(3, 1));
+    }
+}
@@ -13,8 +13,10 @@ use std::{
 
 use crate::{
     array::{Array, ArrayValue},
+    complex::Complex,
     function::Function,
     primitive::Primitive,
+    rational::Rational,
     value::Value,
 };
 
@@ -48,22 +50,86 @@ impl GridFmt for u8 {
     }
 }
 
+/// The magnitude at and above which numbers are shown in scientific notation
+pub const SCIENTIFIC_MAX: f64 = 1e16;
+/// The magnitude below which nonzero numbers are shown in scientific notation
+pub const SCIENTIFIC_MIN: f64 = 1e-6;
+
+/// Format a number the way it is shown in arrays
+///
+/// Integers have no decimal point, and other numbers have the fewest
+/// digits that parse back to the same number. Magnitudes outside of
+/// [`SCIENTIFIC_MIN`]..[`SCIENTIFIC_MAX`] use scientific notation, like `1e16`
+/// or `2.5e¯7`. Negative signs are `¯`, and `π`, `τ`, `η`, and `∞` are shown
+/// as their glyphs.
+pub fn format_num(n: f64) -> String {
+    let positive = n.abs();
+    let minus = if n < -0.0 { "¯" } else { "" };
+    if (positive - PI).abs() < f64::EPSILON {
+        format!("{minus}π")
+    } else if (positive - TAU).abs() < f64::EPSILON {
+        format!("{minus}τ")
+    } else if (positive - PI / 2.0).abs() < f64::EPSILON {
+        format!("{minus}η")
+    } else if positive == INFINITY {
+        format!("{minus}∞")
+    } else if positive >= SCIENTIFIC_MAX || positive != 0.0 && positive < SCIENTIFIC_MIN {
+        format!("{minus}{positive:e}").replace('-', "¯")
+    } else {
+        format!("{minus}{positive}")
+    }
+}
+
 impl GridFmt for f64 {
     fn fmt_grid(&self, boxed: bool) -> Grid {
-        let positive = self.abs();
-        let minus = if *self < -0.0 { "¯" } else { "" };
-        let s = if (positive - PI).abs() < f64::EPSILON {
-            format!("{minus}π")
-        } else if (positive - TAU).abs() < f64::EPSILON {
-            format!("{minus}τ")
-        } else if (positive - PI / 2.0).abs() < f64::EPSILON {
-            format!("{minus}η")
-        } else if positive == INFINITY {
-            format!("{minus}∞")
+        vec![boxed_scalar(boxed)
+            .chain(format_num(*self).chars())
+            .collect()]
+    }
+}
+
+impl GridFmt for Complex {
+    fn fmt_grid(&self, boxed: bool) -> Grid {
+        let mut row: Vec<char> = boxed_scalar(boxed).collect();
+        if self.im == 0.0 || self.re != 0.0 {
+            row.extend(self.re.fmt_grid(false).remove(0));
+        }
+        if self.im != 0.0 {
+            if self.re != 0.0 {
+                row.push(if self.im < 0.0 { '-' } else { '+' });
+            }
+            let im = if self.re != 0.0 {
+                self.im.abs()
+            } else {
+                self.im
+            };
+            if im.abs() != 1.0 {
+                row.extend(im.fmt_grid(false).remove(0));
+            } else if im < 0.0 {
+                row.push('¯');
+            }
+            row.push('i');
+        }
+        vec![row]
+    }
+}
+
+impl GridFmt for Rational {
+    fn fmt_grid(&self, boxed: bool) -> Grid {
+        let mut row: Vec<char> = boxed_scalar(boxed).collect();
+        if self.is_nan() {
+            row.extend("NaN".chars());
         } else {
-            format!("{minus}{positive}")
-        };
-        vec![boxed_scalar(boxed).chain(s.chars()).collect()]
+            if self.numer() < 0 {
+                row.push('¯');
+            }
+            row.extend(self.numer().unsigned_abs().to_string().chars());
+            if self.denom() != 1 {
+                row.push('/');
+                row.extend(self.denom().to_string().chars());
+            }
+        }
+        vec![row]
     }
 }
 
@@ -136,6 +202,8 @@ impl GridFmt for Value {
     fn fmt_grid(&self, boxed: bool) -> Grid {
         match self {
             Value::Num(array) => array.fmt_grid(boxed),
+            Value::Complex(array) => array.fmt_grid(boxed),
+            Value::Rational(array) => array.fmt_grid(boxed),
             Value::Byte(array) => array.fmt_grid(boxed),
             Value::Char(array) => array.fmt_grid(boxed),
             Value::Func(array) => array.fmt_grid(boxed),
@@ -380,3 +448,72 @@ fn pad_grid_min(width: usize, height: usize, grid: &mut Grid) {
         }
     }
 }
+
+#[test]
+fn number_formatting() {
+    for (n, expected) in [
+        (0.0, "0"),
+        (-0.0, "0"),
+        (3.0, "3"),
+        (-17.0, "¯17"),
+        (0.1, "0.1"),
+        (1.0 / 3.0, "0.3333333333333333"),
+        (123456789012345.0, "123456789012345"),
+        (1e16, "1e16"),
+        (-2.5e20, "¯2.5e20"),
+        (0.000001, "0.000001"),
+        (2.5e-7, "2.5e¯7"),
+        (f64::NAN, "NaN"),
+        (f64::NEG_INFINITY, "¯∞"),
+        (PI, "π"),
+    ] {
+        assert_eq!(format_num(n), expected, "{n:?}");
+    }
+    let mut env = crate::Uiua::with_native_sys();
+    for n in [0.1, 1.0 / 3.0, 1e16, -2.5e20, 2.5e-7, 1e300, 5e-324] {
+        env.load_str(&format_num(n)).unwrap();
+        assert_eq!(env.take_stack(), [Value::from(n)], "{n:?}");
+    }
+}
+
+#[test]
+fn char_array_display() {
+    let mut env = crate::Uiua::with_native_sys();
+    env.load_str(r#""Hello" @a ⊂"ab" "cd""#).unwrap();
+    let shown: Vec<String> = env.take_stack().iter().map(Value::show).collect();
+    assert_eq!(shown, [r#""abcd""#, "@a", r#""Hello""#]);
+}
+
+#[test]
+fn matrix_display() {
+    let mut env = crate::Uiua::with_native_sys();
+    env.load_str("[1_22_3 444_5_66]").unwrap();
+    let shown = env.pop(1).unwrap().show();
+    let expected = [
+        "╭─           ",
+        "╷   1 22  3  ",
+        "  444  5 66  ",
+        "            ╯",
+    ];
+    assert_eq!(shown, expected.join("\n"));
+
+    env.load_str("↯2_2_2⇡8").unwrap();
+    let shown = env.pop(1).unwrap().show();
+    let expected = [
+        "╭─     ",
+        "╷ 0 1  ",
+        "╷ 2 3  ",
+        "       ",
+        "  4 5  ",
+        "  6 7  ",
+        "      ╯",
+    ];
+    assert_eq!(shown, expected.join("\n"));
+}
+
+#[test]
+fn box_display() {
+    let mut env = crate::Uiua::with_native_sys();
+    env.load_str(r#"[box 1 box "hi"]"#).unwrap();
+    assert_eq!(env.pop(1).unwrap().show(), "[□1 ⌜hi⌟]");
+}
@@ -467,6 +467,7 @@ impl Lexer {
                 '·' => self.end(Primitive::Identity, start),
                 '⍛' => self.end(Primitive::Fill, start),
                 '⌂' => self.end(Primitive::Rise, start),
+                '≅' => self.end(Primitive::Match, start),
 
                 '(' => self.end(OpenParen, start),
                 ')' => self.end(CloseParen, start),
@@ -12,6 +12,7 @@ pub mod array;
 pub mod ast;
 mod check;
 mod compile;
+pub mod complex;
 mod cowslice;
 mod error;
 pub mod format;
@@ -23,7 +24,9 @@ pub mod parse;
 pub mod primitive;
 #[doc(hidden)]
 pub mod profile;
+pub mod rational;
 pub mod run;
+mod stack;
 mod sys;
 pub mod value;
 #[cfg(feature = "terminal_image")]
@@ -2,13 +2,15 @@
 compile_error!("To compile the uiua interpreter binary, you must enable the `binary` feature flag");
 
 use std::{
+    collections::HashMap,
     env, fmt, fs,
-    io::{self, stderr, Write},
+    io::{self, stderr, stdin, stdout, IsTerminal, Read, Write},
     path::{Path, PathBuf},
     process::{exit, Child, Command, Stdio},
+    str::FromStr,
     sync::mpsc::channel,
     thread::sleep,
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 
 use clap::{error::ErrorKind, Parser};
@@ -18,7 +20,7 @@ use notify::{EventKind, RecursiveMode, Watcher};
 use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use uiua::{
-    format::{format_file, FormatConfig, FormatConfigSource},
+    format::{check_file, format_file, format_str, FormatConfig, FormatConfigSource},
     run::RunMode,
     Uiua, UiuaError, UiuaResult,
 };
@@ -47,6 +49,24 @@ fn main() {
     }
 }
 
+fn print_primitive_profile(rt: &Uiua) {
+    let profile = rt.primitive_profile();
+    let total: f64 = profile.iter().map(|(_, p)| p.time).sum();
+    eprintln!(
+        "{:<16} {:>10} {:>12} {:>6}",
+        "primitive", "calls", "time (ms)", "%"
+    );
+    for (prim, p) in profile {
+        let name = prim.name().map_or_else(|| prim.to_string(), Into::into);
+        let percent = if total > 0.0 {
+            p.time / total * 100.0
+        } else {
+            0.0
+        };
+        eprintln!("{name:<16} {:>10} {:>12.3} {percent:>6.1}", p.calls, p.time);
+    }
+}
+
 static WATCH_CHILD: Lazy<Mutex<Option<Child>>> = Lazy::new(Default::default);
 
 fn run() -> UiuaResult {
@@ -67,13 +87,41 @@ fn run() -> UiuaResult {
             App::Fmt {
                 path,
                 formatter_options,
+                check,
             } => {
+                let from_stdin = path.as_ref().is_some_and(|path| path.as_os_str() == "-");
                 let config = FormatConfig::from_source(
                     formatter_options.format_config_source,
-                    path.as_deref(),
+                    path.as_deref().filter(|_| !from_stdin),
                 )?;
 
-                if let Some(path) = path {
+                if from_stdin {
+                    let mut input = String::new();
+                    stdin()
+                        .read_to_string(&mut input)
+                        .map_err(|e| UiuaError::Load("stdin".into(), e.into()))?;
+                    let output = format_str(&input, &config)?.output;
+                    if check {
+                        if output != input {
+                            println!("stdin is not formatted");
+                            exit(1);
+                        }
+                    } else {
+                        print!("{output}");
+                    }
+                } else if check {
+                    let paths = path.map_or_else(uiua_files, |path| vec![path]);
+                    let mut unformatted = 0;
+                    for path in paths {
+                        if !check_file(&path, &config)? {
+                            println!("{} is not formatted", path.display());
+                            unformatted += 1;
+                        }
+                    }
+                    if unformatted > 0 {
+                        exit(1);
+                    }
+                } else if let Some(path) = path {
                     format_single_file(path, &config, formatter_options.stdout)?;
                 } else {
                     format_multi_files(&config, formatter_options.stdout)?;
@@ -85,6 +133,10 @@ fn run() -> UiuaResult {
                 formatter_options,
                 no_update,
                 mode,
+                no_write,
+                seed,
+                output,
+                profile,
                 #[cfg(feature = "audio")]
                 audio_options,
                 args,
@@ -117,14 +169,27 @@ fn run() -> UiuaResult {
                     .with_mode(mode)
                     .with_file_path(&path)
                     .with_args(args)
-                    .print_diagnostics(true);
+                    .allow_file_writes(!no_write)
+                    .print_diagnostics(true)
+                    .profile_primitives(profile);
+                if let Some(seed) = seed {
+                    rt = rt.with_seed(seed);
+                }
                 rt.load_file(path)?;
+                if profile {
+                    print_primitive_profile(&rt);
+                }
+                let output = output.unwrap_or(OutputFormat::Text);
                 for value in rt.take_stack() {
-                    println!("{}", value.show());
+                    match output {
+                        OutputFormat::Text => println!("{}", value.show()),
+                        OutputFormat::Json => println!("{}", value.to_json()),
+                    }
                 }
             }
             App::Eval {
                 code,
+                seed,
                 #[cfg(feature = "audio")]
                 audio_options,
                 args,
@@ -134,7 +199,11 @@ fn run() -> UiuaResult {
                 let mut rt = Uiua::with_native_sys()
                     .with_mode(RunMode::Normal)
                     .with_args(args)
+                    .allow_file_writes(true)
                     .print_diagnostics(true);
+                if let Some(seed) = seed {
+                    rt = rt.with_seed(seed);
+                }
                 rt.load_str(&code)?;
                 for value in rt.take_stack() {
                     println!("{}", value.show());
@@ -158,17 +227,34 @@ fn run() -> UiuaResult {
                 let config =
                     FormatConfig::from_source(formatter_options.format_config_source, Some(&path))?;
                 format_file(&path, &config)?;
-                Uiua::with_native_sys()
+                let mut rt = Uiua::with_native_sys()
                     .with_mode(RunMode::Test)
-                    .print_diagnostics(true)
-                    .load_file(path)?;
-                println!("No failures!");
+                    .allow_file_writes(true)
+                    .print_diagnostics(true);
+                rt.load_file(path)?;
+                let results = rt.take_test_results();
+                if results.failures.is_empty() {
+                    println!("No failures! ({} assertions passed)", results.passed);
+                } else {
+                    for failure in &results.failures {
+                        eprintln!("{}", failure.show(true));
+                    }
+                    eprintln!(
+                        "{} assertions passed, {} failed",
+                        results.passed,
+                        results.failures.len()
+                    );
+                    exit(1);
+                }
             }
             App::Watch {
+                path,
                 no_format,
                 formatter_options,
                 no_update,
                 clear,
+                watch_clear,
+                debounce,
                 args,
                 stdin_file,
             } => {
@@ -176,16 +262,25 @@ fn run() -> UiuaResult {
                     show_update_message();
                 }
                 if let Err(e) = watch(
-                    working_file_path().ok().as_deref(),
+                    &WatchTarget::resolve(path.as_deref()),
                     !no_format,
                     formatter_options.format_config_source,
                     clear,
+                    watch_clear,
+                    Duration::from_millis(debounce),
                     args,
                     stdin_file,
                 ) {
                     eprintln!("Error watching file: {e}");
                 }
             }
+            App::Repl { formatter_options } => {
+                let config =
+                    FormatConfig::from_source(formatter_options.format_config_source, None)?;
+                if let Err(e) = repl(&config) {
+                    eprintln!("Error reading input: {e}");
+                }
+            }
             #[cfg(feature = "lsp")]
             App::Lsp => uiua::lsp::run_server(),
         },
@@ -193,18 +288,22 @@ fn run() -> UiuaResult {
             show_update_message();
             let res = match working_file_path() {
                 Ok(path) => watch(
-                    Some(&path),
+                    &WatchTarget::current_dir(Some(path)),
                     true,
                     FormatConfigSource::SearchFile,
                     false,
+                    WatchClear::On,
+                    DEFAULT_DEBOUNCE,
                     Vec::new(),
                     None,
                 ),
                 Err(NoWorkingFile::MultipleFiles) => watch(
-                    None,
+                    &WatchTarget::current_dir(None),
                     true,
                     FormatConfigSource::SearchFile,
                     false,
+                    WatchClear::On,
+                    DEFAULT_DEBOUNCE,
                     Vec::new(),
                     None,
                 ),
@@ -247,18 +346,27 @@ impl fmt::Display for NoWorkingFile {
 }
 
 fn working_file_path() -> Result<PathBuf, NoWorkingFile> {
-    let main_in_src = PathBuf::from("src/main.ua");
+    working_file_path_in(Path::new(""))
+}
+
+fn working_file_path_in(dir: &Path) -> Result<PathBuf, NoWorkingFile> {
+    let main_in_src = dir.join("src/main.ua");
     let main = if main_in_src.exists() {
         main_in_src
     } else {
-        PathBuf::from("main.ua")
+        dir.join("main.ua")
     };
     if main.exists() {
         Ok(main)
     } else {
-        let paths: Vec<_> = fs::read_dir(".")
+        let read_dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        let paths: Vec<_> = fs::read_dir(read_dir)
             .into_iter()
-            .chain(fs::read_dir("src"))
+            .chain(fs::read_dir(dir.join("src")))
             .flatten()
             .filter_map(Result::ok)
             .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "ua"))
@@ -272,23 +380,255 @@ fn working_file_path() -> Result<PathBuf, NoWorkingFile> {
     }
 }
 
+#[test]
+fn working_file_fallback() {
+    let dir = env::temp_dir().join("uiua_working_file_test");
+    fs::create_dir_all(&dir).unwrap();
+    assert!(matches!(
+        working_file_path_in(&dir),
+        Err(NoWorkingFile::NoFile)
+    ));
+    let single = dir.join("single.ua");
+    fs::write(&single, "").unwrap();
+    assert_eq!(working_file_path_in(&dir).unwrap(), single);
+    fs::write(dir.join("other.ua"), "").unwrap();
+    assert!(matches!(
+        working_file_path_in(&dir),
+        Err(NoWorkingFile::MultipleFiles)
+    ));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+/// What `uiua watch` watches for changes
+#[derive(Debug, PartialEq)]
+struct WatchTarget {
+    /// The directory to watch
+    dir: PathBuf,
+    /// If set, only changes to this file are run, and `dir` is not watched recursively
+    file: Option<PathBuf>,
+    /// The file to run when watching starts
+    initial: Option<PathBuf>,
+}
+
+impl WatchTarget {
+    fn resolve(path: Option<&Path>) -> Self {
+        match path {
+            Some(path) if path.is_file() => {
+                let dir = (path.parent())
+                    .filter(|dir| !dir.as_os_str().is_empty())
+                    .unwrap_or(Path::new("."));
+                WatchTarget {
+                    dir: dir.into(),
+                    file: Some(path.into()),
+                    initial: Some(path.into()),
+                }
+            }
+            Some(dir) => WatchTarget {
+                dir: dir.into(),
+                file: None,
+                initial: working_file_path_in(dir).ok(),
+            },
+            None => Self::current_dir(working_file_path().ok()),
+        }
+    }
+    fn current_dir(initial: Option<PathBuf>) -> Self {
+        WatchTarget {
+            dir: ".".into(),
+            file: None,
+            initial,
+        }
+    }
+    fn recursive_mode(&self) -> RecursiveMode {
+        if self.file.is_some() {
+            RecursiveMode::NonRecursive
+        } else {
+            RecursiveMode::Recursive
+        }
+    }
+    /// Check if a changed path should be run
+    fn matches(&self, path: &Path) -> bool {
+        if !path.extension().is_some_and(|ext| ext == "ua") {
+            return false;
+        }
+        let Some(file) = &self.file else {
+            return true;
+        };
+        let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.into());
+        canonical(file) == canonical(path)
+    }
+}
+
+#[test]
+fn watch_target_resolution() {
+    let dir = env::temp_dir().join("uiua_watch_target_test");
+    let multi = dir.join("multi");
+    fs::create_dir_all(&multi).unwrap();
+    let main = dir.join("main.ua");
+    let a = multi.join("a.ua");
+    let b = multi.join("b.ua");
+    for path in [&main, &a, &b] {
+        fs::write(path, "").unwrap();
+    }
+
+    let target = WatchTarget::resolve(Some(&a));
+    assert_eq!(target.dir, multi);
+    assert_eq!(target.file.as_ref(), Some(&a));
+    assert_eq!(target.initial.as_ref(), Some(&a));
+    assert_eq!(target.recursive_mode(), RecursiveMode::NonRecursive);
+    assert!(target.matches(&a));
+    assert!(target.matches(&multi.join(".").join("a.ua")));
+    assert!(!target.matches(&b));
+
+    let target = WatchTarget::resolve(Some(&dir));
+    assert_eq!(target.dir, dir);
+    assert_eq!(target.file, None);
+    assert_eq!(target.initial.as_ref(), Some(&main));
+    assert_eq!(target.recursive_mode(), RecursiveMode::Recursive);
+    assert!(target.matches(&a));
+    assert!(!target.matches(&dir.join("notes.txt")));
+
+    let target = WatchTarget::resolve(Some(&multi));
+    assert_eq!(target.initial, None);
+
+    let target = WatchTarget::resolve(Some(Path::new("Cargo.toml")));
+    assert_eq!(target.dir, Path::new("."));
+    fs::remove_dir_all(dir).unwrap();
+}
+
+const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(50);
+
+/// Collects file change events until they stop coming for a while
+///
+/// Editors often emit several events for a single save, so running
+/// on every event would run the same file multiple times.
+struct Debouncer {
+    window: Duration,
+    /// Pairs of canonical paths and the paths as they were reported
+    pending: Vec<(PathBuf, PathBuf)>,
+    last_event: Option<Instant>,
+}
+
+impl Debouncer {
+    fn new(window: Duration) -> Self {
+        Debouncer {
+            window,
+            pending: Vec::new(),
+            last_event: None,
+        }
+    }
+    fn push(&mut self, path: PathBuf, now: Instant) {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.clone());
+        if !self.pending.iter().any(|(c, _)| *c == canonical) {
+            self.pending.push((canonical, path));
+        }
+        self.last_event = Some(now);
+    }
+    /// Take the changed paths if no events have come in for the debounce window
+    fn ready(&mut self, now: Instant) -> Vec<PathBuf> {
+        match self.last_event {
+            Some(last) if now.duration_since(last) >= self.window => {
+                self.last_event = None;
+                self.pending.drain(..).map(|(_, path)| path).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+#[test]
+fn debounce_burst() {
+    let dir = env::temp_dir().join("uiua_debounce_test");
+    fs::create_dir_all(&dir).unwrap();
+    let a = dir.join("a.ua");
+    let b = dir.join("b.ua");
+    fs::write(&a, "").unwrap();
+    fs::write(&b, "").unwrap();
+    let mut debouncer = Debouncer::new(Duration::from_millis(50));
+    let start = Instant::now();
+    for i in 0..5 {
+        let time = start + Duration::from_millis(i * 10);
+        debouncer.push(a.clone(), time);
+        debouncer.push(dir.join(".").join("a.ua"), time);
+        assert!(debouncer.ready(time).is_empty());
+    }
+    let settled = start + Duration::from_millis(100);
+    assert_eq!(debouncer.ready(settled), std::slice::from_ref(&a));
+    assert!(debouncer.ready(settled).is_empty());
+    debouncer.push(a.clone(), settled);
+    debouncer.push(b.clone(), settled);
+    let paths = debouncer.ready(settled + Duration::from_millis(50));
+    assert_eq!(paths, [a, b]);
+    fs::remove_dir_all(dir).unwrap();
+}
+
+/// Decides whether a changed file should be run
+///
+/// Formatting a file in watch writes it, which is reported as another change.
+/// A file is not run again if it has not been modified since it last ran.
+/// Saving a file always runs it, even if its contents are the same, so that
+/// programs can be re-run after their imports change or to get new input.
+/// A failure is forgotten so that the next change after an error always runs.
+#[derive(Default)]
+struct RunHistory {
+    last_run: HashMap<PathBuf, SystemTime>,
+}
+
+impl RunHistory {
+    /// Record that a file was last modified at `modified`, returning whether it should be run
+    fn should_run(&mut self, path: &Path, modified: Option<SystemTime>) -> bool {
+        let Some(modified) = modified else {
+            return true;
+        };
+        if self.last_run.get(path) == Some(&modified) {
+            return false;
+        }
+        self.last_run.insert(path.into(), modified);
+        true
+    }
+    /// Record that a file failed to load or format
+    fn failed(&mut self, path: &Path) {
+        self.last_run.remove(path);
+    }
+}
+
+#[test]
+fn run_history() {
+    let a = Path::new("a.ua");
+    let b = Path::new("b.ua");
+    let time = |secs| Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+    let mut history = RunHistory::default();
+    assert!(history.should_run(a, time(1)));
+    assert!(!history.should_run(a, time(1)));
+    assert!(history.should_run(b, time(1)));
+    assert!(history.should_run(a, time(2)));
+    assert!(history.should_run(a, None));
+    assert!(history.should_run(a, None));
+    history.failed(a);
+    assert!(history.should_run(a, time(2)));
+    assert!(!history.should_run(a, time(2)));
+    assert!(!history.should_run(b, time(1)));
+}
+
+#[allow(clippy::too_many_arguments)]
 fn watch(
-    initial_path: Option<&Path>,
+    target: &WatchTarget,
     format: bool,
     format_config_source: FormatConfigSource,
     clear: bool,
+    watch_clear: WatchClear,
+    debounce: Duration,
     args: Vec<String>,
     stdin_file: Option<PathBuf>,
 ) -> io::Result<()> {
     let (send, recv) = channel();
     let mut watcher = notify::recommended_watcher(send).unwrap();
     watcher
-        .watch(Path::new("."), RecursiveMode::Recursive)
+        .watch(&target.dir, target.recursive_mode())
         .unwrap_or_else(|e| panic!("Failed to watch directory: {e}"));
 
     println!("Watching for changes... (end with ctrl+C, use `uiua help` to see options)");
 
-    let config = FormatConfig::from_source(format_config_source, initial_path).ok();
+    let config = FormatConfig::from_source(format_config_source, target.initial.as_deref()).ok();
     #[cfg(feature = "audio")]
     let audio_time = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0f64.to_bits()));
     #[cfg(feature = "audio")]
@@ -300,11 +640,28 @@ fn watch(
         socket.set_nonblocking(true)?;
         (socket, port)
     };
-    let run = |path: &Path, stdin_file: Option<&PathBuf>| -> io::Result<()> {
+    let separate = || match watch_clear {
+        WatchClear::On => clear_watching(),
+        WatchClear::Off => println!(),
+    };
+    let start_output = |clear: bool| {
         if let Some(mut child) = WATCH_CHILD.lock().take() {
             _ = child.kill();
             print_watching();
         }
+        if clear {
+            if cfg!(target_os = "windows") {
+                _ = Command::new("cmd").args(["/C", "cls"]).status();
+            } else {
+                _ = Command::new("clear").status();
+            }
+        }
+        separate();
+    };
+    let mut history = RunHistory::default();
+    let mut run = |path: &Path, stdin_file: Option<&PathBuf>, clear: bool| -> io::Result<()> {
+        // The initial path and event paths may be spelled differently
+        let key = path.canonicalize().unwrap_or_else(|_| path.into());
         const TRIES: u8 = 10;
         for i in 0..TRIES {
             let formatted = if let (Some(config), true) = (&config, format) {
@@ -314,12 +671,16 @@ fn watch(
             };
             match formatted {
                 Ok(input) => {
+                    // Read the modification time after formatting, which may write the file
+                    let modified = fs::metadata(path).and_then(|meta| meta.modified()).ok();
+                    if !history.should_run(&key, modified) {
+                        return Ok(());
+                    }
+                    start_output(clear);
                     if input.is_empty() {
-                        clear_watching();
                         print_watching();
                         return Ok(());
                     }
-                    clear_watching();
                     #[cfg(feature = "audio")]
                     let audio_time =
                         f64::from_bits(audio_time_clone.load(std::sync::atomic::Ordering::Relaxed))
@@ -336,6 +697,7 @@ fn watch(
                             .args([
                                 "--no-format",
                                 "--no-update",
+                                "--no-write",
                                 "--mode",
                                 "all",
                                 #[cfg(feature = "audio")]
@@ -356,7 +718,8 @@ fn watch(
                 }
                 Err(UiuaError::Format(..)) => sleep(Duration::from_millis((i as u64 + 1) * 10)),
                 Err(e) => {
-                    clear_watching();
+                    history.failed(&key);
+                    start_output(clear);
                     println!("{}", e.show(true));
                     print_watching();
                     return Ok(());
@@ -366,31 +729,24 @@ fn watch(
         println!("Failed to format file after {TRIES} tries");
         Ok(())
     };
-    if let Some(path) = initial_path {
-        run(path, stdin_file.as_ref())?;
+    if let Some(path) = &target.initial {
+        run(path, stdin_file.as_ref(), false)?;
     }
-    let mut last_time = Instant::now();
+    let mut debouncer = Debouncer::new(debounce);
     loop {
         sleep(Duration::from_millis(10));
-        if let Some(path) = recv
+        for path in recv
             .try_iter()
             .filter_map(Result::ok)
-            .filter(|event| matches!(event.kind, EventKind::Modify(_)))
+            .filter(|event| matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)))
             .flat_map(|event| event.paths)
-            .filter(|path| path.extension().map_or(false, |ext| ext == "ua"))
-            .last()
+            .filter(|path| target.matches(path))
         {
-            if last_time.elapsed() > Duration::from_millis(100) {
-                if clear {
-                    if cfg!(target_os = "windows") {
-                        _ = Command::new("cmd").args(["/C", "cls"]).status();
-                    } else {
-                        _ = Command::new("clear").status();
-                    }
-                }
-                run(&path, stdin_file.as_ref())?;
-                last_time = Instant::now();
-            }
+            debouncer.push(path, Instant::now());
+        }
+        let paths = debouncer.ready(Instant::now());
+        for path in &paths {
+            run(path, stdin_file.as_ref(), clear)?;
         }
         let mut child = WATCH_CHILD.lock();
         if let Some(ch) = &mut *child {
@@ -426,6 +782,17 @@ enum App {
         no_update: bool,
         #[clap(long, help = "Run the file in a specific mode")]
         mode: Option<RunMode>,
+        #[clap(long, help = "Don't allow the program to write files")]
+        no_write: bool,
+        #[clap(long, help = "Seed the random number generator")]
+        seed: Option<u64>,
+        #[clap(
+            long,
+            help = "Print the final stack in a specific format (text or json)"
+        )]
+        output: Option<OutputFormat>,
+        #[clap(long, help = "Print the time spent in each primitive after running")]
+        profile: bool,
         #[cfg(feature = "audio")]
         #[clap(flatten)]
         audio_options: AudioOptions,
@@ -435,6 +802,8 @@ enum App {
     #[clap(about = "Evaluate an expression and print its output")]
     Eval {
         code: String,
+        #[clap(long, help = "Seed the random number generator")]
+        seed: Option<u64>,
         #[cfg(feature = "audio")]
         #[clap(flatten)]
         audio_options: AudioOptions,
@@ -449,6 +818,8 @@ enum App {
     },
     #[clap(about = "Run .ua files in the current directory when they change")]
     Watch {
+        #[clap(help = "A file or directory to watch instead of the current directory")]
+        path: Option<PathBuf>,
         #[clap(long, help = "Don't format the file before running")]
         no_format: bool,
         #[clap(flatten)]
@@ -457,6 +828,18 @@ enum App {
         no_update: bool,
         #[clap(long, help = "Clear the terminal on file change")]
         clear: bool,
+        #[clap(
+            long,
+            default_value = "on",
+            help = "Whether to replace the watching message with a separator line (on or off)"
+        )]
+        watch_clear: WatchClear,
+        #[clap(
+            long,
+            default_value_t = 50,
+            help = "Milliseconds to wait for file changes to settle before running"
+        )]
+        debounce: u64,
         #[clap(long, help = "Read stdin from file")]
         stdin_file: Option<PathBuf>,
         #[clap(trailing_var_arg = true)]
@@ -464,15 +847,57 @@ enum App {
     },
     #[clap(about = "Format a uiua file or all files in the current directory")]
     Fmt {
+        #[clap(help = "The file to format, or - to read from stdin and write to stdout")]
         path: Option<PathBuf>,
         #[clap(flatten)]
         formatter_options: FormatterOptions,
+        #[clap(long, help = "Check that files are formatted without changing them")]
+        check: bool,
+    },
+    #[clap(about = "Start an interactive session")]
+    Repl {
+        #[clap(flatten)]
+        formatter_options: FormatterOptions,
     },
     #[cfg(feature = "lsp")]
     #[clap(about = "Run the Language Server")]
     Lsp,
 }
 
+#[derive(Clone, Copy)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!("unknown output format `{}`", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum WatchClear {
+    On,
+    Off,
+}
+
+impl FromStr for WatchClear {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "on" => Ok(WatchClear::On),
+            "off" => Ok(WatchClear::Off),
+            _ => Err(format!("Invalid watch clear setting: {s}")),
+        }
+    }
+}
+
 #[derive(clap::Args)]
 struct FormatterOptions {
     #[clap(
@@ -531,11 +956,85 @@ fn clear_watching() {
 }
 
 fn clear_watching_with(s: &str, end: &str) {
-    print!(
-        "\r{}{}",
-        s.repeat(term_size::dimensions().map_or(10, |(w, _)| w)),
-        end,
-    );
+    let width = stdout()
+        .is_terminal()
+        .then(|| term_size::dimensions().map_or(10, |(w, _)| w));
+    print!("{}", clear_watching_text(s, end, width));
+}
+
+/// The text that clears the watching message
+///
+/// `width` is `None` if the output is not a terminal, in which case
+/// there is no message to overwrite.
+fn clear_watching_text(s: &str, end: &str, width: Option<usize>) -> String {
+    match width {
+        Some(width) => format!("\r{}{end}", s.repeat(width)),
+        None => end.into(),
+    }
+}
+
+#[test]
+fn clear_watching_non_tty() {
+    assert_eq!(clear_watching_text("―", "\n", None), "\n");
+    assert_eq!(clear_watching_text(" ", "", None), "");
+    assert_eq!(clear_watching_text("―", "\n", Some(3)), "\r―――\n");
+}
+
+fn repl(config: &FormatConfig) -> io::Result<()> {
+    let mut rt = Uiua::with_native_sys()
+        .allow_file_writes(true)
+        .print_diagnostics(true);
+    let mut line = String::new();
+    loop {
+        print!("» ");
+        stdout().flush()?;
+        line.clear();
+        if stdin().read_line(&mut line)? == 0 {
+            println!();
+            return Ok(());
+        }
+        match repl_line(&mut rt, &line, config) {
+            Ok(Some(formatted)) => {
+                println!("{formatted}");
+                if let Some(top) = rt.clone_stack_top(1).pop() {
+                    println!("{}", top.show());
+                }
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("{}", e.show(true)),
+        }
+    }
+}
+
+/// Run a single line of REPL input, returning the formatted code
+///
+/// The `clear` command empties the stack.
+fn repl_line(rt: &mut Uiua, line: &str, config: &FormatConfig) -> UiuaResult<Option<String>> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+    if line == "clear" {
+        rt.take_stack();
+        return Ok(None);
+    }
+    let formatted = format_str(line, config)?.output;
+    let formatted = formatted.trim_end().to_string();
+    rt.load_str(&formatted)?;
+    Ok(Some(formatted))
+}
+
+#[test]
+fn repl_keeps_stack() {
+    let mut rt = Uiua::with_native_sys();
+    let config = FormatConfig::default();
+    repl_line(&mut rt, "1 2", &config).unwrap();
+    assert_eq!(repl_line(&mut rt, "+", &config).unwrap().unwrap(), "+");
+    let shown: Vec<String> = rt.take_stack().iter().map(|val| val.show()).collect();
+    assert_eq!(shown, ["3"]);
+    repl_line(&mut rt, "5", &config).unwrap();
+    repl_line(&mut rt, "clear", &config).unwrap();
+    assert!(rt.take_stack().is_empty());
 }
 
 fn show_update_message() {
@@ -17,6 +17,11 @@ pub enum ParseError {
     InvalidArgCount(String),
     InvalidOutCount(String),
     AmpersandBindingName,
+    MissingModifierOperands {
+        modifier: Primitive,
+        expected: u8,
+        found: usize,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -78,6 +83,17 @@ impl fmt::Display for ParseError {
             ParseError::InvalidArgCount(n) => write!(f, "Invalid argument count `{n}`"),
             ParseError::InvalidOutCount(n) => write!(f, "Invalid output count `{n}`"),
             ParseError::AmpersandBindingName => write!(f, "Binding names may not contain `&`"),
+            ParseError::MissingModifierOperands {
+                modifier,
+                expected,
+                found,
+            } => write!(
+                f,
+                "`{}` expects {expected} function operand{}, but {found} {} given",
+                modifier.name().unwrap_or("modifier"),
+                if *expected == 1 { "" } else { "s" },
+                if *found == 1 { "was" } else { "were" }
+            ),
         }
     }
 }
@@ -416,6 +432,18 @@ impl Parser {
                 break;
             }
         }
+        if !terminated && args.len() < margs as usize {
+            let found = args.len();
+            let span = (args.last()).map_or(modifier.span.clone(), |arg| {
+                modifier.span.clone().merge(arg.span.clone())
+            });
+            self.errors
+                .push(span.sp(ParseError::MissingModifierOperands {
+                    modifier: modifier.value,
+                    expected: margs,
+                    found,
+                }));
+        }
         Some(if args.is_empty() {
             modifier.map(Word::Primitive)
         } else {
@@ -538,3 +566,26 @@ impl Parser {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Uiua, UiuaErrorKind};
+
+    #[test]
+    fn missing_modifier_operands() {
+        for (code, expected) in [
+            (
+                "reduce",
+                "`reduce` expects 1 function operand, but 0 were given",
+            ),
+            ("/", "`reduce` expects 1 function operand, but 0 were given"),
+            ("⊃+", "`fork` expects 2 function operands, but 1 was given"),
+        ] {
+            let error = Uiua::with_native_sys().load_str(code).unwrap_err();
+            assert_eq!(error.kind(), UiuaErrorKind::Parse, "{code}");
+            let message = error.message();
+            assert!(message.contains(expected), "{message}");
+        }
+        assert!(Uiua::with_native_sys().load_str("/+ [1 2]").is_ok());
+    }
+}
@@ -137,8 +137,8 @@ primitive!(
     /// This is a very common pattern.
     ///
     /// [duplicate] can be used to make a monadic left-hook, such as in this palindrome checker:
-    /// ex: ≅⇌. "friend"
-    /// ex: ≅⇌. "racecar"
+    /// ex: ≍⇌. "friend"
+    /// ex: ≍⇌. "racecar"
     /// Another commonly hooked function is [keep].
     /// ex: ▽=0◿3. [1 4 2 3 9 1 0 6 2 6 3]
     (1(2), Dup, Stack, ("duplicate", '.')),
@@ -212,11 +212,13 @@ primitive!(
     /// ex: ¬0
     /// ex: ¬1
     /// ex: ¬[0 1 1 0]
+    ///
+    /// Any nonzero number counts as true, so its [not] is `0`.
     /// ex: ¬[0 1 2 3]
+    /// ex: ¬¯0.5
     ///
-    /// This is equivalent to `subtract``flip``1`
-    /// ex: ¬7
-    /// ex: ¬[1 2 3 4]
+    /// To get `1` minus a number, use `subtract``flip``1` instead.
+    /// ex: -∶1 [0.25 2]
     (1, Not, MonadicPervasive, ("not", '¬')),
     /// Numerical sign (1, ¯1, or 0)
     ///
@@ -250,7 +252,25 @@ primitive!(
     /// ex: √4
     /// ex: √[1 4 9 16]
     /// ex: √¯1
+    ///
+    /// If any of the numbers are negative, the result is [complex].
+    /// ex: √[4 ¯4]
     (1, Sqrt, MonadicPervasive, ("sqrt", '√')),
+    /// The real part of a number
+    ///
+    /// ex: real complex 3 4
+    /// ex: real 5
+    (1, Real, MonadicPervasive, "real"),
+    /// The imaginary part of a number
+    ///
+    /// ex: imag complex 3 4
+    /// ex: imag 5
+    (1, Imag, MonadicPervasive, "imag"),
+    /// Convert an exact rational number to a regular number
+    ///
+    /// ex: float rational 1 4
+    /// ex: float 5
+    (1, Float, MonadicPervasive, "float"),
     /// The sine of a number
     ///
     /// ex: ○ 1
@@ -292,6 +312,81 @@ primitive!(
     /// ex: ⁅1.5
     /// ex: ⁅[0.1 π 2 9.9 7.5]
     (1, Round, MonadicPervasive, ("round", '⁅')),
+    /// Round to the nearest integer, rounding halves to even
+    ///
+    /// [round] rounds halves away from zero, which biases sums of rounded numbers.
+    /// [halfeven] rounds them to the nearest even integer instead.
+    /// ex: halfeven [0.5 1.5 2.5 3.5 ¯2.5]
+    /// ex: ⁅ [0.5 1.5 2.5 3.5 ¯2.5]
+    ///
+    /// Other numbers are rounded the same as [round].
+    /// ex: halfeven [1.2 ¯1.7 2.51]
+    (1, HalfEven, MonadicPervasive, "halfeven"),
+    /// Round to the nearest multiple of a step
+    ///
+    /// This is like [round], but the first argument controls the granularity.
+    /// ex: nearest 0.01 π
+    /// ex: nearest 5 [12 13 ¯7]
+    /// The sign of the step is ignored, and a step of `0` leaves the value unchanged.
+    /// ex: nearest 0 1.5
+    (2, Nearest, DyadicPervasive, "nearest"),
+    /// Round down to a multiple of a step
+    ///
+    /// This is like [floor], but the first argument controls the granularity.
+    /// ex: below 5 13
+    /// ex: below 0.5 [1.2 ¯1.2]
+    (2, Below, DyadicPervasive, "below"),
+    /// Round up to a multiple of a step
+    ///
+    /// This is like [ceiling], but the first argument controls the granularity.
+    /// ex: above 5 13
+    /// ex: above 0.5 [1.2 ¯1.2]
+    (2, Above, DyadicPervasive, "above"),
+    /// Constrain a value to a range
+    ///
+    /// The first two arguments are the bounds of the range. The result is the third argument, but no less than the lower bound and no greater than the upper bound.
+    /// ex: clamp 0 10 ¯5
+    /// ex: clamp 0 10 15
+    /// ex: clamp 0 10 [¯5 5 15]
+    /// The bounds can be arrays too.
+    /// ex: clamp [0 1 2] 3 [5 0 2]
+    /// The order of the bounds does not matter.
+    /// ex: clamp 10 0 [¯5 5 15]
+    (3, Clamp, Misc, "clamp"),
+    /// Check which numbers are `NaN`
+    ///
+    /// `NaN`s can come from operations like dividing `0` by `0`.
+    /// ex: isnan ÷0 0
+    /// ex: isnan [1 ÷0 0 ∞]
+    /// ex: ▽¬isnan. [1 ÷0 0 2]
+    (1, IsNan, MonadicPervasive, "isnan"),
+    /// Check which numbers are infinite
+    ///
+    /// ex: isinf ∞
+    /// ex: isinf [1 ÷0 1 ¯∞ ÷0 0]
+    (1, IsInf, MonadicPervasive, "isinf"),
+    /// Replace `NaN`s with a value
+    ///
+    /// ex: nanfill 0 [1 ÷0 0 3]
+    /// ex: nanfill [5 6 7] [÷0 0 2 ÷0 0]
+    (2, NanFill, DyadicPervasive, "nanfill"),
+    /// Get the factorial of a natural number
+    ///
+    /// ex: factorial 5
+    /// ex: factorial 0
+    /// ex: factorial [1 2 3 4 5 6]
+    /// Results are computed as floating point numbers, so factorials of numbers greater than `170` are [infinity].
+    /// ex: factorial 171
+    /// ex! factorial ¯1
+    /// ex! factorial 2.5
+    (1, Factorial, MonadicPervasive, "factorial"),
+    /// Flip all the bits of an integer
+    ///
+    /// Integers are treated as 64-bit two's complement numbers.
+    /// ex: bitnot 0
+    /// ex: bitnot [1 2 3]
+    /// ex! bitnot 1.5
+    (1, BitNot, MonadicPervasive, "bitnot"),
     /// Compare for equality
     ///
     /// ex: =1 2
@@ -299,6 +394,13 @@ primitive!(
     /// ex: =1 [1 2 3]
     /// ex: = [1 2 2] [1 2 3]
     (2, Eq, DyadicPervasive, ("equals", AsciiToken::Equal, '=')),
+    /// Compare for equality within a tolerance
+    ///
+    /// The first argument is the tolerance. The result is `1` where the other two arguments differ by at most the tolerance.
+    /// ex: approxeq 0.001 0.1 0.1000005
+    /// ex: approxeq 0.001 0.1 0.2
+    /// ex: approxeq 0.5 1 [0 0.75 1 1.5 2]
+    (3, ApproxEq, Misc, "approxeq"),
     /// Compare for inequality
     ///
     /// Formats from `!=`.
@@ -415,7 +517,27 @@ primitive!(
     /// ex: ◿10 27
     /// ex: ◿5 [3 7 14]
     /// ex: ◿ [3 4 5] [10 10 10]
+    ///
+    /// The result always has the sign of the divisor, so it is the floored modulus.
+    /// ex: ◿3 ¯1
+    /// ex: ◿¯3 [¯5 5]
     (2, Mod, DyadicPervasive, ("modulus", '◿')),
+    /// Get both the floored quotient and the modulus of a division
+    ///
+    /// The quotient is on top of the stack, and the modulus is below it.
+    /// ex: divmod 3 17
+    /// ex: [divmod 3 17]
+    /// The modulus is the same as [modulus], and the two results always satisfy `b = a × q + r`.
+    /// ex: [divmod 3 ¯17]
+    /// ex: [divmod ¯3 [17 ¯17]]
+    (2(2), DivMod, DyadicPervasive, "divmod"),
+    /// Get the absolute difference between two numbers
+    ///
+    /// This is the same as [absolute value][subtract], and it does not depend on the order of the arguments.
+    /// ex: distance 3 7
+    /// ex: distance 7 3
+    /// ex: distance [1 5 ¯2] [4 1 2]
+    (2, AbsDiff, DyadicPervasive, "distance"),
     /// Raise a value to a power
     ///
     /// The second value is raised to the power of the first.
@@ -457,6 +579,82 @@ primitive!(
     /// ex: ∠ ¯1 0
     /// ex: ∠ √2 √2
     (2, Atan, DyadicPervasive, ("atangent", '∠')),
+    /// Make a complex number from a real and an imaginary part
+    ///
+    /// The first argument is the real part, and the second is the imaginary part.
+    /// ex: complex 3 4
+    /// ex: complex [1 2 3] 1
+    /// Complex numbers work with arithmetic primitives.
+    /// Real numbers are promoted to complex numbers as needed.
+    /// ex: × complex 1 2 complex 3 4
+    /// ex: + 1 complex 0 1
+    /// ex: ⌵ complex 3 4
+    (2, Complex, DyadicPervasive, "complex"),
+    /// Make an exact rational number from a numerator and a denominator
+    ///
+    /// ex: rational 1 3
+    /// ex: rational [1 2 3] 4
+    /// Rational numbers stay exact through [add], [subtract], [multiply], and [divide].
+    /// ex: + rational 1 3 rational 1 6
+    /// ex: ÷ rational 3 1 rational 1 2
+    /// Mixing a rational number with a non-rational one gives a non-rational result.
+    /// ex: + 0.5 rational 1 3
+    /// ex: × 2 rational 1 3
+    /// ex! rational 1 0
+    /// ex! ÷ rational 0 1 rational 1 2
+    /// Use [float] to turn a rational number back into a regular one.
+    (2, Rational, DyadicPervasive, "rational"),
+    /// The greatest common divisor of two integers
+    ///
+    /// ex: gcd 12 18
+    /// ex: gcd 12 [8 9 10]
+    /// The result is never negative.
+    /// ex: gcd ¯4 6
+    /// ex: gcd 0 0
+    /// ex! gcd 1.5 3
+    (2, Gcd, DyadicPervasive, "gcd"),
+    /// The least common multiple of two integers
+    ///
+    /// ex: lcm 4 6
+    /// ex: lcm 3 [4 5 6]
+    /// If either argument is `0`, the result is `0`.
+    /// ex: lcm 0 5
+    /// ex! lcm 2.5 3
+    (2, Lcm, DyadicPervasive, "lcm"),
+    /// Logical AND two numbers
+    ///
+    /// Any nonzero number counts as true. The result is `1` if both arguments are true and `0` otherwise.
+    /// ex: and [1 0 1] [1 1 0]
+    /// ex: and 1 [0 2 ¯3]
+    /// Both arguments are always evaluated. For booleans, this is the same as [minimum].
+    /// ex: ↧ [1 0 1] [1 1 0]
+    (2, And, DyadicPervasive, "and"),
+    /// Logical OR two numbers
+    ///
+    /// Any nonzero number counts as true. The result is `1` if either argument is true and `0` otherwise.
+    /// ex: or [1 0 0] [1 1 0]
+    /// ex: or 0 [0 2 ¯3]
+    /// Both arguments are always evaluated. For booleans, this is the same as [maximum].
+    /// ex: ↥ [1 0 0] [1 1 0]
+    (2, Or, DyadicPervasive, "or"),
+    /// Bitwise AND two integers
+    ///
+    /// ex: bitand 6 3
+    /// ex: bitand 1 [1 2 3 4 5]
+    /// ex! bitand 1.5 3
+    (2, BitAnd, DyadicPervasive, "bitand"),
+    /// Bitwise OR two integers
+    ///
+    /// ex: bitor 6 3
+    /// ex: bitor 1 [1 2 3 4 5]
+    /// ex! bitor 1.5 3
+    (2, BitOr, DyadicPervasive, "bitor"),
+    /// Bitwise XOR two integers
+    ///
+    /// ex: bitxor 5 3
+    /// ex: bitxor 1 [1 2 3 4 5]
+    /// ex! bitxor 1.5 3
+    (2, BitXor, DyadicPervasive, "bitxor"),
     /// The number of rows in an array
     ///
     /// ex: ⧻5
@@ -464,9 +662,14 @@ primitive!(
     /// ex: ⧻1_2_3
     /// ex: ⧻[1_2 3_4 5_6]
     ///
-    /// [length] is equivalent to the [first] of the [shape].
+    /// [length] is equivalent to the [first] of the [shape], except that a scalar has [length] `1`.
     /// ex:  ⧻[1_2_3 4_5_6]
     ///   : ⊢△[1_2_3 4_5_6]
+    ///
+    /// The [length] of an array of boxes counts the boxes, not their contents.
+    /// A single [box] is looked through, so its [length] is the [length] of its contents.
+    /// ex: ⧻[□1 □[2 3]]
+    /// ex: ⧻□[1 2 3]
     (1, Len, MonadicArray, ("length", '⧻')),
     /// The dimensions of an array
     ///
@@ -475,6 +678,11 @@ primitive!(
     /// ex: △1_2_3
     /// ex: △[1_2 3_4 5_6]
     ///
+    /// The [shape] of an array of boxes is the shape of the outer array.
+    /// A single [box] is looked through, so its [shape] is the [shape] of its contents.
+    /// ex: △[□1 □[2 3]]
+    /// ex: △□[1 2 3]
+    ///
     /// It is a triangle`△` because a triangle is a shape.
     (1, Shape, MonadicArray, ("shape", '△')),
     /// Make an array of all natural numbers less than a number
@@ -489,6 +697,15 @@ primitive!(
     ///   :   ⇡△[1_2_3 4_5_6]
     ///   : ⊡⇡△.[1_2_3 4_5_6]
     (1, Range, MonadicArray, ("range", '⇡')),
+    /// Make an array of the indices of every element of an array
+    ///
+    /// This is the same as [range] of the [shape].
+    /// ex: indices [5 6 7]
+    /// ex: indices [1_2 3_4]
+    ///
+    /// [pick]ing the indices from the original array gives it back.
+    /// ex: ⊡indices. [1_2_3 4_5_6]
+    (1, Indices, MonadicArray, "indices"),
     /// The first row of an array
     ///
     /// ex: ⊢1_2_3
@@ -503,6 +720,17 @@ primitive!(
     /// ex: ⇌1_2_3_9
     /// ex: ⇌[1_2 3_4 5_6]
     (1, Reverse, MonadicArray, ("reverse", '⇌')),
+    /// Reverse an array along an axis
+    ///
+    /// The first argument is the index of the axis to reverse.
+    /// [mirror]ing axis `0` is the same as [reverse].
+    /// ex: mirror 0 [1_2_3 4_5_6]
+    /// ex: mirror 1 [1_2_3 4_5_6]
+    /// ex: mirror 2 ↯2_2_3⇡12
+    ///
+    /// The axis must be less than the rank of the array.
+    /// ex! mirror 2 [1_2_3 4_5_6]
+    (2, Mirror, DyadicArray, "mirror"),
     /// Make an array 1-dimensional
     ///
     /// ex: ♭5
@@ -563,6 +791,25 @@ primitive!(
     /// Here, we sort the array descending by the [absolute value] of its elements.
     /// ex: ⊏⍖⌵.6_2_7_0_¯1_5
     (1, Fall, MonadicArray, ("fall", '⍖')),
+    /// Sort an array ascending
+    ///
+    /// The rows of the array are sorted lexicographically.
+    /// ex: ⍆6_2_7_0_¯1_5
+    /// ex: ⍆[3_1 1_2 3_0 1_1]
+    ///
+    /// [sort] is equivalent to [select] by the [rise].
+    /// ex: ≍ ⍆∶ ⊏⍏. . "uiua"
+    ///
+    /// To sort descending, use [sortdown].
+    (1, Sort, MonadicArray, ("sort", '⍆')),
+    /// Sort an array descending
+    ///
+    /// The rows of the array are sorted lexicographically.
+    /// ex: sortdown 6_2_7_0_¯1_5
+    /// ex: sortdown [3_1 1_2 3_0 1_1]
+    ///
+    /// [sortdown] is equivalent to [select] by the [fall].
+    (1, SortDown, MonadicArray, "sortdown"),
     /// Assign a unique index to each unique element in an array
     ///
     /// ex: ⊛7_7_8_0_1_2_0
@@ -620,9 +867,11 @@ primitive!(
     (1, Unbox, MonadicArray, ("unbox", '⊔')),
     /// Check if two arrays are exactly the same
     ///
-    /// ex: ≅ 1_2_3 [1 2 3]
-    /// ex: ≅ 1_2_3 [1 2]
-    (2, Match, DyadicArray, ("match", '≅')),
+    /// Unlike [equals], this always returns a single boolean.
+    /// Arrays with different shapes do not match.
+    /// ex: ≍ 1_2_3 [1 2 3]
+    /// ex: ≍ 1_2_3 [1 2]
+    (2, Match, DyadicArray, ("match", '≍')),
     /// Combine two arrays as rows of a new array
     ///
     /// `first``shape` of the coupled array will *always* be `2`.
@@ -719,6 +968,14 @@ primitive!(
     ///
     /// See also: [deshape]
     (2, Reshape, DyadicArray, ("reshape", '↯')),
+    /// Change the shape of an array without repeating or dropping elements
+    ///
+    /// The new shape must have exactly as many elements as the original array.
+    /// ex: exactreshape 2_3 [1 2 3 4 5 6]
+    /// ex! exactreshape 2_3 [1 2 3 4 5]
+    ///
+    /// See also: [reshape]
+    (2, ReshapeExact, DyadicArray, "exactreshape"),
     /// Take the first n elements of an array
     ///
     /// This is the opposite of [drop].
@@ -762,6 +1019,47 @@ primitive!(
     /// Multi-dimensional rotations are supported.
     /// ex: ↻1_2 .↯4_5⇡20
     (2, Rotate, DyadicArray, ("rotate", '↻')),
+    /// Rotate the elements of an array along an axis
+    ///
+    /// The first argument is the index of the axis, and the second is the rotation amount.
+    /// ex: spin 1 1 [1_2_3 4_5_6]
+    /// ex: spin 1 ¯1 ↯2_2_3⇡12
+    ///
+    /// [spin]ning axis `0` is the same as [rotate].
+    /// ex: spin 0 1 [1_2_3 4_5_6]
+    ///
+    /// A list of amounts rotates the axis and the ones after it.
+    /// ex: spin 1 1_1 ↯2_2_3⇡12
+    (3, Spin, Misc, "spin"),
+    /// Replace the elements of an array where a mask is set
+    ///
+    /// The first argument is a mask of booleans with the same shape as the array. The second is the replacement.
+    /// ex: maskreplace [1 0 1] 0 [1 2 3]
+    /// ex: maskreplace ∶0 <0 . [¯1 2 ¯3 4]
+    /// The replacement can be a scalar or an array. A replacement array's shape must be a prefix of the array's shape.
+    /// ex: maskreplace [1 0 1] [10 20 30] [1 2 3]
+    /// ex: maskreplace ∶[1 2] =0 . [0_3 4_0]
+    /// ex! maskreplace [1 0] 0 [1 2 3]
+    (3, MaskReplace, Misc, "maskreplace"),
+    /// Choose each element from one of two arrays
+    ///
+    /// The first argument is a mask of booleans. Where it is `1`, the element is taken from the second argument. Where it is `0`, the element is taken from the third.
+    /// ex: cond [1 0 1] [10 20 30] [1 2 3]
+    /// All three arguments are broadcast against each other, so any of them can be a scalar.
+    /// ex: cond [1 0 1] 0 [1 2 3]
+    /// ex: cond <0 . [¯2 3 ¯1 4] 0
+    /// ex: cond [1 0] [1_2 3_4] 0
+    /// ex! cond [1 0] [1 2 3] 0
+    (3, Cond, Misc, "cond"),
+    /// Solve a system of linear equations
+    ///
+    /// The first argument is a square matrix `A`, and the second is a vector `b`. The result is the vector `x` such that `A` times `x` is `b`.
+    /// ex: solve [2_1 1_3] [4 7]
+    /// ex: solve [1_1_1 0_2_5 2_5_¯1] [6 ¯4 27]
+    /// The system must have exactly one solution.
+    /// ex! solve [1_2 2_4] [3 6]
+    /// ex! solve [1_2_3 4_5_6] [1 2]
+    (2, Solve, DyadicArray, "solve"),
     /// The n-wise windows of an array
     ///
     /// ex: ◫2 .⇡4
@@ -770,6 +1068,15 @@ primitive!(
     /// Multi-dimensional window sizes are supported.
     /// ex: ◫2_2 .[1_2_3 4_5_6 7_8_9]
     (2, Windows, DyadicArray, ("windows", '◫')),
+    /// Split an array into chunks of some number of rows
+    ///
+    /// Unlike [windows], the chunks do not overlap.
+    /// ex: chunks 2 [1 2 3 4 5 6]
+    /// ex: chunks 2 .↯4_3⇡12
+    ///
+    /// The number of rows must be divisible by the chunk size, so that every chunk has the same shape.
+    /// ex! chunks 2 [1 2 3 4 5]
+    (2, Chunks, DyadicArray, "chunks"),
     /// Discard or copy some rows of an array
     ///
     /// Takes two arrays. The first array is the number of copies to keep of each row of the second array.
@@ -795,9 +1102,13 @@ primitive!(
     (3, Unkeep, Misc),
     /// Find the occurences of one array in another
     ///
+    /// The result is a mask with the same shape as the searched array.
+    /// It has a `1` wherever a match starts.
     /// ex: ⌕ 5 [1 8 5 2 3 5 4 5 6 7]
     /// ex: ⌕ "ab" "abracadabra"
     /// ex: ⌕ 1_2 . ↯4_4⇡3
+    /// An array that is longer than the searched array is never found.
+    /// ex: ⌕ "abc" "ab"
     (2, Find, DyadicArray, ("find", '⌕')),
     /// Check if each row of one array exists in another
     ///
@@ -847,6 +1158,10 @@ primitive!(
     /// You can can reduce with arbitrary functions.
     /// ex: /(×+1) 1_2_3_4_5
     ///
+    /// Reducing an empty list with [add], [multiply], [maximum], [minimum], or [bitor] gives that function's identity.
+    /// ex: /↥ []
+    /// ex: /× []
+    ///
     /// [break]ing out of [reduce] discards the unreduced values.
     /// ex: /(⎋≥10.+) [3 4 8 9]
     (1[1], Reduce, AggregatingModifier, ("reduce", '/')),
@@ -1297,6 +1612,19 @@ primitive!(
     ///
     /// Errors thrown by [assert] can be caught with [try].
     (2(0), Assert, Control, ("assert", '⍤')),
+    /// Throw an error if an array does not have a given shape
+    ///
+    /// The first argument is the expected shape. If the second argument has that shape, it is returned unchanged.
+    /// ex: assertshape 2_3 ↯2_3⇡6
+    /// ex! assertshape 3_2 ↯2_3⇡6
+    /// ex: assertshape [] 5
+    ///
+    /// This is useful for checking the arguments to a function.
+    /// ex: Sum ← /+assertshape [3]
+    ///   : Sum [1 2 3]
+    ///
+    /// Errors thrown by [assertshape] can be caught with [try].
+    (2, AssertShape, Control, "assertshape"),
     /// Spawn a thread
     ///
     /// Expects a function.
@@ -1375,8 +1703,52 @@ primitive!(
     ///
     /// ex: parse "17"
     /// ex: parse "3.1415926535897932"
+    /// ex: parse "¯5"
+    /// Whitespace-separated numbers parse into a list.
+    /// ex: parse "1 2 ¯3.5"
     /// ex! parse "dog"
     (1, Parse, Misc, "parse"),
+    /// Convert a value to its string representation
+    ///
+    /// This uses the same formatting as when values are printed.
+    /// ex: show 17
+    /// ex: show ¯3.5
+    /// ex: show [1 2 3]
+    /// Multidimensional arrays span multiple lines.
+    /// ex: show [1_2 3_4]
+    /// [parse] is the inverse for single numbers.
+    /// ex: parse show ¯3.5
+    (1, Show, Misc, "show"),
+    /// Parse a JSON string into an array
+    ///
+    /// Numbers become numbers, and strings become character arrays.
+    /// ex: parsejson "[1, 2, 3]"
+    /// Lists whose items have the same type and shape become multidimensional arrays.
+    /// ex: parsejson "[[1, 2], [3, 4]]"
+    /// Other lists become lists of boxes.
+    /// ex: parsejson "[1, [2, 3]]"
+    /// Objects are not yet supported.
+    /// ex! parsejson "{}"
+    (1, ParseJson, Misc, "parsejson"),
+    /// Encode a string as UTF-8 bytes
+    ///
+    /// ex: utf "hello"
+    /// ex: utf "héllo"
+    /// [invert][utf] decodes UTF-8 bytes back into a string.
+    /// ex: ⍘utf [240 159 146 150]
+    /// ex! ⍘utf [255 254]
+    (1, Utf8, Misc, "utf"),
+    /// Inverse of Utf8
+    (1, InvUtf8, Misc),
+    /// Split a Unix timestamp into its date and time parts
+    ///
+    /// The timestamp is the number of seconds since the Unix epoch, like what [&n] returns.
+    /// The result is `[year month day hour minute second]` in UTC.
+    /// ex: dateparts 0
+    /// ex: dateparts 1700000000
+    /// It works on arrays of timestamps.
+    /// ex: dateparts [86399 951782400]
+    (1, DateParts, Misc, "dateparts"),
     /// Generate a random number between 0 and 1
     ///
     /// If you need a seeded random number, use [gen].
@@ -1427,11 +1799,13 @@ primitive!(
     /// `0` indicates a number array.
     /// `1` indicates a character array.
     /// `2` indicates a function array.
+    /// `3` indicates a complex array.
+    /// `4` indicates a rational array.
     /// ex: type 5
     /// ex: type "hello"
     /// ex: type (+)
-    /// ex: ∵type  {10 "dog" (≅⇌.)}
-    ///   : ∵(|1 type!) {10 "dog" (≅⇌.)}
+    /// ex: ∵type  {10 "dog" (≍⇌.)}
+    ///   : ∵(|1 type!) {10 "dog" (≍⇌.)}
     (1, Type, Misc, "type"),
     /// The number of radians in a quarter circle
     ///
@@ -1449,6 +1823,9 @@ primitive!(
     /// ex: [×4η ×2π τ]
     (0, Tau, Constant, ("tau", 'τ')),
     /// The biggest number
+    ///
+    /// ex: ∞
+    /// ex: ↧∞ [5 ¯2 7]
     (0, Infinity, Constant, ("infinity", '∞')),
     /// Debug print the top value on the stack without popping it
     ///
@@ -7,7 +7,6 @@ pub use defs::*;
 
 use std::{
     borrow::Cow,
-    cell::RefCell,
     f64::{
         consts::{PI, TAU},
         INFINITY,
@@ -26,6 +25,7 @@ use rand::prelude::*;
 
 use crate::{
     algorithm::{fork, loops},
+    array::FormatShape,
     function::Function,
     grid_fmt::GridFmt,
     lex::AsciiToken,
@@ -154,9 +154,14 @@ impl Primitive {
     pub fn from_simple(s: AsciiToken) -> Option<Self> {
         Self::all().find(|p| p.ascii() == Some(s))
     }
+    /// Find a primitive by its glyph
     pub fn from_unicode(c: char) -> Option<Self> {
         Self::all().find(|p| p.unicode() == Some(c))
     }
+    /// Find a primitive by its ASCII spelling, like `!=`
+    pub fn from_ascii(s: &str) -> Option<Self> {
+        Self::all().find(|p| p.ascii().is_some_and(|a| a.to_string() == s))
+    }
     pub fn is_modifier(&self) -> bool {
         self.modifier_args().is_some()
     }
@@ -188,6 +193,8 @@ impl Primitive {
             InvTranspose => Transpose,
             Bits => InverseBits,
             InverseBits => Bits,
+            Utf8 => InvUtf8,
+            InvUtf8 => Utf8,
             Couple => Uncouple,
             Roll => Unroll,
             Unroll => Roll,
@@ -219,11 +226,26 @@ impl Primitive {
         if name.len() < 3 {
             return None;
         }
-        let mut matching =
-            Primitive::all().filter(|p| p.names().is_some_and(|n| n.text.starts_with(name)));
-        let res = matching.next()?;
-        let exact_match = res.names().unwrap().text == name;
-        (exact_match || matching.next().is_none()).then_some(res)
+        let matching: Vec<Self> = Primitive::all()
+            .filter(|p| p.name().is_some_and(|n| n.starts_with(name)))
+            .collect();
+        if let [prim] = matching[..] {
+            return Some(prim);
+        }
+        // An ambiguous prefix refers to the only matching primitive with a glyph,
+        // or else to the one whose name the other matching names extend
+        let with_glyph: Vec<Self> = matching
+            .iter()
+            .filter(|p| p.unicode().is_some())
+            .copied()
+            .collect();
+        if let [prim] = with_glyph[..] {
+            return Some(prim);
+        }
+        matching.iter().copied().find(|p| {
+            let prefix = p.name().unwrap();
+            (matching.iter()).all(|q| q.name().unwrap().starts_with(prefix))
+        })
     }
     /// Try to parse multiple primitives from the concatenation of their name prefixes
     pub fn from_format_name_multi(name: &str) -> Option<Vec<(Self, &str)>> {
@@ -259,23 +281,125 @@ impl Primitive {
             _ => return None,
         })
     }
+    /// The value that a dyadic primitive leaves its other argument unchanged with
+    ///
+    /// [`Primitive::Reduce`] returns this when folding an empty array.
+    pub fn identity_value(&self) -> Option<Value> {
+        Some(Value::from(match self {
+            Primitive::Add | Primitive::BitOr | Primitive::Or => 0.0,
+            Primitive::Mul | Primitive::And => 1.0,
+            Primitive::Max => f64::NEG_INFINITY,
+            Primitive::Min => f64::INFINITY,
+            _ => return None,
+        }))
+    }
     pub(crate) fn run(&self, env: &mut Uiua) -> UiuaResult {
         match self {
-            Primitive::Eta => env.push(PI / 2.0),
-            Primitive::Pi => env.push(PI),
-            Primitive::Tau => env.push(TAU),
-            Primitive::Infinity => env.push(INFINITY),
-            Primitive::Identity => env.touch_array_stack(),
             Primitive::Gap => {
                 let f = env.pop(1)?;
                 let _x = env.pop(2)?;
                 env.call(f)?;
             }
+            Primitive::Call => {
+                let f = env.pop(1)?;
+                let sig = f.signature();
+                if !(sig.outputs == 1 || sig == (0, 0)) {
+                    return Err(env.error(format!(
+                        "Only functions with 1 output may be explicitly called, \
+                        but the function {f} has {} outputs",
+                        sig.outputs
+                    )));
+                }
+                env.call(f)?
+            }
+            Primitive::Fold => loops::fold(env)?,
+            Primitive::Reduce => loops::reduce(env)?,
+            Primitive::Each => loops::each(env)?,
+            Primitive::Rows => loops::rows(env)?,
+            Primitive::Distribute => loops::distribute(env)?,
+            Primitive::Table => loops::table(env)?,
+            Primitive::Cross => loops::cross(env)?,
+            Primitive::Scan => loops::scan(env)?,
+            Primitive::Repeat => loops::repeat(env)?,
+            Primitive::Level => loops::level(env)?,
+            Primitive::Group => loops::group(env)?,
+            Primitive::Partition => loops::partition(env)?,
+            Primitive::Recur => {
+                let n = env.pop(1)?.as_nat(env, "Recur expects a natural number")?;
+                env.recur(n)?;
+            }
+            Primitive::Dip => {
+                let f = env.pop(FunctionArg(1))?;
+                let x = env.pop(1)?;
+                env.call(f)?;
+                env.push(x);
+            }
+            Primitive::Invert => {
+                let f = env.pop(FunctionArg(1))?;
+                let inv_f = f.invert(env)?;
+                env.call(inv_f)?;
+            }
+            Primitive::Under => {
+                let f = env.pop(FunctionArg(1))?;
+                let g = env.pop(FunctionArg(2))?;
+                let (f_before, f_after) = f.under(env)?;
+                env.call(f_before)?;
+                env.call(g)?;
+                env.call(f_after)?;
+            }
+            Primitive::Fill => {
+                let fill = env.pop(FunctionArg(1))?;
+                let f = env.pop(FunctionArg(2))?;
+                env.with_fill(fill, |env| env.call(f))?;
+            }
+            Primitive::Both => fork::both(env)?,
+            Primitive::Fork => fork::fork(env)?,
+            Primitive::Bracket => fork::bracket(env)?,
+            Primitive::If => fork::iff(env)?,
+            Primitive::Try => {
+                let f = env.pop(FunctionArg(1))?;
+                let handler = env.pop(FunctionArg(2))?;
+                let f_args = f.signature().args;
+                let backup = env.clone_stack_top(f_args);
+                let bottom = env.stack_size().saturating_sub(f_args);
+                if let Err(e) = env.call(f) {
+                    env.truncate_stack(bottom);
+                    env.backend.save_error_color(&e);
+                    env.push(e.value());
+                    for val in backup {
+                        env.push(val);
+                    }
+                    env.call(handler)?;
+                }
+            }
+            Primitive::Spawn => {
+                let f = env.pop("thread function")?;
+                let handle = env.spawn(f.signature().args, |env| env.call(f))?;
+                env.push(handle);
+            }
+            Primitive::Sys(io) => io.run(env)?,
+            prim => prim.run_value(env)?,
+        }
+        Ok(())
+    }
+    /// Run a primitive that does not call any functions
+    ///
+    /// This is separate from [`Primitive::run`] because [`Primitive::run`] is
+    /// on the native stack once for every nested call, so its frame should be small.
+    fn run_value(&self, env: &mut Uiua) -> UiuaResult {
+        match self {
+            Primitive::Eta => env.push(PI / 2.0),
+            Primitive::Pi => env.push(PI),
+            Primitive::Tau => env.push(TAU),
+            Primitive::Infinity => env.push(INFINITY),
+            Primitive::Identity => env.touch_array_stack(),
             Primitive::Not => env.monadic_env(Value::not)?,
             Primitive::Neg => env.monadic_env(Value::neg)?,
             Primitive::Abs => env.monadic_env(Value::abs)?,
             Primitive::Sign => env.monadic_env(Value::sign)?,
-            Primitive::Sqrt => env.monadic_env(Value::sqrt)?,
+            Primitive::Sqrt => env.monadic_env(|val, env| val.complex_if_negative().sqrt(env))?,
+            Primitive::Real => env.monadic_env(Value::real)?,
+            Primitive::Imag => env.monadic_env(Value::imag)?,
             Primitive::Sin => env.monadic_env(Value::sin)?,
             Primitive::Cos => env.monadic_env(Value::cos)?,
             Primitive::Asin => env.monadic_env(Value::asin)?,
@@ -283,22 +407,112 @@ impl Primitive {
             Primitive::Floor => env.monadic_env(Value::floor)?,
             Primitive::Ceil => env.monadic_env(Value::ceil)?,
             Primitive::Round => env.monadic_env(Value::round)?,
+            Primitive::IsNan => env.monadic_env(Value::isnan)?,
+            Primitive::IsInf => env.monadic_env(Value::isinf)?,
+            Primitive::NanFill => env.dyadic_rr_env(Value::nanfill)?,
+            Primitive::HalfEven => env.monadic_env(Value::halfeven)?,
+            Primitive::Factorial => env.monadic_env(|val, env| {
+                val.require_naturals(env, "Argument to factorial must be a natural number")?;
+                val.factorial(env)
+            })?,
+            Primitive::BitNot => env.monadic_env(|val, env| {
+                val.require_integers(env, "Argument to bitnot must be an integer")?;
+                val.bitnot(env)
+            })?,
             Primitive::Eq => env.dyadic_rr_env(Value::is_eq)?,
             Primitive::Ne => env.dyadic_rr_env(Value::is_ne)?,
             Primitive::Lt => env.dyadic_rr_env(Value::is_lt)?,
             Primitive::Le => env.dyadic_rr_env(Value::is_le)?,
+            Primitive::ApproxEq => {
+                let tolerance = env.pop(1)?;
+                let a = env.pop(2)?;
+                let b = env.pop(3)?;
+                let diff = a.sub(&b, env)?.abs(env)?;
+                env.push(tolerance.is_le(&diff, env)?);
+            }
+            Primitive::Clamp => {
+                let a = env.pop(1)?;
+                let b = env.pop(2)?;
+                let x = env.pop(3)?;
+                let low = Value::min(&a, &b, env)?;
+                let high = Value::max(&a, &b, env)?;
+                let clamped = Value::min(&high, &x, env)?;
+                env.push(Value::max(&low, &clamped, env)?);
+            }
             Primitive::Gt => env.dyadic_rr_env(Value::is_gt)?,
             Primitive::Ge => env.dyadic_rr_env(Value::is_ge)?,
             Primitive::Add => env.dyadic_rr_env(Value::add)?,
             Primitive::Sub => env.dyadic_rr_env(Value::sub)?,
             Primitive::Mul => env.dyadic_rr_env(Value::mul)?,
-            Primitive::Div => env.dyadic_rr_env(Value::div)?,
+            Primitive::Div => env.dyadic_rr_env(|a, b, env| {
+                if let (Value::Rational(_), Value::Rational(_) | Value::Byte(_))
+                | (Value::Byte(_), Value::Rational(_)) = (a, b)
+                {
+                    a.require_nonzero(env, "Cannot divide a rational number by zero")?;
+                }
+                a.div(b, env)
+            })?,
             Primitive::Mod => env.dyadic_rr_env(Value::modulus)?,
+            Primitive::AbsDiff => env.dyadic_rr_env(|a, b, env| a.sub(b, env)?.abs(env))?,
+            Primitive::DivMod => {
+                let a = env.pop(1)?;
+                let b = env.pop(2)?;
+                let modulus = a.modulus(&b, env)?;
+                let quotient = a.div(&b, env)?.floor(env)?;
+                env.push(modulus);
+                env.push(quotient);
+            }
+            Primitive::Nearest => env.dyadic_rr_env(Value::nearest)?,
+            Primitive::Below => env.dyadic_rr_env(Value::below)?,
+            Primitive::Above => env.dyadic_rr_env(Value::above)?,
             Primitive::Pow => env.dyadic_rr_env(Value::pow)?,
             Primitive::Log => env.dyadic_rr_env(Value::log)?,
             Primitive::Min => env.dyadic_rr_env(Value::min)?,
             Primitive::Max => env.dyadic_rr_env(Value::max)?,
             Primitive::Atan => env.dyadic_rr_env(Value::atan2)?,
+            Primitive::Complex => env.dyadic_rr_env(Value::complex)?,
+            Primitive::Rational => env.dyadic_rr_env(|a, b, env| {
+                a.require_integers(env, "Numerator must be an integer")?;
+                b.require_integers(env, "Denominator must be an integer")?;
+                a.require_i64(env, "Numerator must fit in 64 bits")?;
+                b.require_i64(env, "Denominator must fit in 64 bits")?;
+                b.require_nonzero(env, "Denominator must not be zero")?;
+                a.rational(b, env)
+            })?,
+            Primitive::Float => env.monadic_env(Value::float)?,
+            Primitive::Gcd => env.dyadic_rr_env(|a, b, env| {
+                a.require_finite(env, "Arguments to gcd must be finite")?;
+                b.require_finite(env, "Arguments to gcd must be finite")?;
+                a.require_integers(env, "Arguments to gcd must be integers")?;
+                b.require_integers(env, "Arguments to gcd must be integers")?;
+                a.gcd(b, env)
+            })?,
+            Primitive::Lcm => env.dyadic_rr_env(|a, b, env| {
+                a.require_finite(env, "Arguments to lcm must be finite")?;
+                b.require_finite(env, "Arguments to lcm must be finite")?;
+                a.require_integers(env, "Arguments to lcm must be integers")?;
+                b.require_integers(env, "Arguments to lcm must be integers")?;
+                let lcm = a.lcm(b, env)?;
+                lcm.require_exact(env, "The lcm must be exact")?;
+                Ok(lcm)
+            })?,
+            Primitive::And => env.dyadic_rr_env(Value::and)?,
+            Primitive::Or => env.dyadic_rr_env(Value::or)?,
+            Primitive::BitAnd => env.dyadic_rr_env(|a, b, env| {
+                a.require_integers(env, "Arguments to bitand must be integers")?;
+                b.require_integers(env, "Arguments to bitand must be integers")?;
+                a.bitand(b, env)
+            })?,
+            Primitive::BitOr => env.dyadic_rr_env(|a, b, env| {
+                a.require_integers(env, "Arguments to bitor must be integers")?;
+                b.require_integers(env, "Arguments to bitor must be integers")?;
+                a.bitor(b, env)
+            })?,
+            Primitive::BitXor => env.dyadic_rr_env(|a, b, env| {
+                a.require_integers(env, "Arguments to bitxor must be integers")?;
+                b.require_integers(env, "Arguments to bitxor must be integers")?;
+                a.bitxor(b, env)
+            })?,
             Primitive::Match => env.dyadic_rr(|a, b| a == b)?,
             Primitive::Join => env.dyadic_oo_env(Value::join)?,
             Primitive::Transpose => env.monadic_mut(Value::transpose)?,
@@ -325,6 +539,25 @@ impl Primitive {
                 env.push(from.undrop(index, into, env)?);
             }
             Primitive::Rotate => env.dyadic_ro_env(Value::rotate)?,
+            Primitive::Spin => {
+                let axis = env.pop(1)?.as_nat(env, "Axis must be a natural number")?;
+                let by = env.pop(2)?;
+                let rotated = env.pop(3)?;
+                env.push(by.rotate_axis(axis, rotated, env)?);
+            }
+            Primitive::MaskReplace => {
+                let mask = env.pop(1)?;
+                let replacement = env.pop(2)?;
+                let into = env.pop(3)?;
+                env.push(mask.mask_replace(replacement, into, env)?);
+            }
+            Primitive::Solve => env.dyadic_rr_env(Value::solve)?,
+            Primitive::Cond => {
+                let mask = env.pop(1)?;
+                let then = env.pop(2)?;
+                let otherwise = env.pop(3)?;
+                env.push(mask.select_where(then, otherwise, env)?);
+            }
             Primitive::Couple => env.dyadic_oo_env(Value::couple)?,
             Primitive::Uncouple => {
                 let coupled = env.pop(1)?;
@@ -334,6 +567,8 @@ impl Primitive {
             }
             Primitive::Rise => env.monadic_ref_env(|v, env| v.rise(env))?,
             Primitive::Fall => env.monadic_ref_env(|v, env| v.fall(env))?,
+            Primitive::Sort => env.monadic_env(Value::sort)?,
+            Primitive::SortDown => env.monadic_env(Value::sort_down)?,
             Primitive::Pick => env.dyadic_oo_env(Value::pick)?,
             Primitive::Unpick => {
                 let from = env.pop(1)?;
@@ -349,6 +584,7 @@ impl Primitive {
                 env.push(from.unselect(index, into, env)?);
             }
             Primitive::Windows => env.dyadic_rr_env(Value::windows)?,
+            Primitive::Chunks => env.dyadic_ro_env(Value::chunks)?,
             Primitive::Classify => env.monadic_ref_env(Value::classify)?,
             Primitive::Deduplicate => env.monadic_mut(Value::deduplicate)?,
             Primitive::Member => env.dyadic_rr_env(Value::member)?,
@@ -366,21 +602,23 @@ impl Primitive {
                 }
                 env.push(val);
             }
-            Primitive::Call => {
-                let f = env.pop(1)?;
-                let sig = f.signature();
-                if !(sig.outputs == 1 || sig == (0, 0)) {
-                    return Err(env.error(format!(
-                        "Only functions with 1 output may be explicitly called, \
-                        but the function {f} has {} outputs",
-                        sig.outputs
-                    )));
-                }
-                env.call(f)?
-            }
             Primitive::Parse => env.monadic_env(|v, env| v.parse_num(env))?,
+            Primitive::Show => env.monadic_ref(Value::show)?,
+            Primitive::Utf8 => env.monadic_ref_env(Value::utf8)?,
+            Primitive::InvUtf8 => env.monadic_ref_env(Value::inv_utf8)?,
+            Primitive::ParseJson => env.monadic_ref_env(|v, env| {
+                Value::from_json(&v.as_string(env, "JSON must be a string")?, env)
+            })?,
+            Primitive::DateParts => env.monadic_ref_env(Value::date_parts)?,
             Primitive::Range => env.monadic_ref_env(Value::range)?,
+            Primitive::Indices => env.monadic_ref_env(Value::indices)?,
             Primitive::Reverse => env.monadic_mut(Value::reverse)?,
+            Primitive::Mirror => {
+                let axis = env.pop(1)?.as_nat(env, "Axis must be a natural number")?;
+                let mut array = env.pop(2)?;
+                array.reverse_axis(axis, env)?;
+                env.push(array);
+            }
             Primitive::Deshape => env.monadic_mut(Value::deshape)?,
             Primitive::First => env.monadic_env(Value::first)?,
             Primitive::Last => env.monadic_env(Value::last)?,
@@ -390,34 +628,24 @@ impl Primitive {
             }
             Primitive::Bits => env.monadic_ref_env(Value::bits)?,
             Primitive::InverseBits => env.monadic_ref_env(Value::inverse_bits)?,
-            Primitive::Fold => loops::fold(env)?,
-            Primitive::Reduce => loops::reduce(env)?,
-            Primitive::Each => loops::each(env)?,
-            Primitive::Rows => loops::rows(env)?,
-            Primitive::Distribute => loops::distribute(env)?,
-            Primitive::Table => loops::table(env)?,
-            Primitive::Cross => loops::cross(env)?,
-            Primitive::Scan => loops::scan(env)?,
-            Primitive::Repeat => loops::repeat(env)?,
-            Primitive::Level => loops::level(env)?,
-            Primitive::Group => loops::group(env)?,
-            Primitive::Partition => loops::partition(env)?,
             Primitive::Reshape => {
                 let shape = env.pop(1)?;
                 let mut array = env.pop(2)?;
                 array.reshape(&shape, env)?;
                 env.push(array);
             }
+            Primitive::ReshapeExact => {
+                let shape = env.pop(1)?;
+                let mut array = env.pop(2)?;
+                array.reshape_exact(&shape, env)?;
+                env.push(array);
+            }
             Primitive::Break => {
                 let n = env.pop(1)?.as_nat(env, "Break expects a natural number")?;
                 if n > 0 {
                     return Err(UiuaError::Break(n - 1, env.span().clone()));
                 }
             }
-            Primitive::Recur => {
-                let n = env.pop(1)?.as_nat(env, "Recur expects a natural number")?;
-                env.recur(n)?;
-            }
             Primitive::Dup => {
                 let x = env.pop(1)?;
                 env.push(x.clone());
@@ -455,31 +683,7 @@ impl Primitive {
                 env.push(a);
                 env.push(c);
             }
-            Primitive::Dip => {
-                let f = env.pop(FunctionArg(1))?;
-                let x = env.pop(1)?;
-                env.call(f)?;
-                env.push(x);
-            }
             Primitive::Restack => fork::restack(env)?,
-            Primitive::Invert => {
-                let f = env.pop(FunctionArg(1))?;
-                let inv_f = f.invert(env)?;
-                env.call(inv_f)?;
-            }
-            Primitive::Under => {
-                let f = env.pop(FunctionArg(1))?;
-                let g = env.pop(FunctionArg(2))?;
-                let (f_before, f_after) = f.under(env)?;
-                env.call(f_before)?;
-                env.call(g)?;
-                env.call(f_after)?;
-            }
-            Primitive::Fill => {
-                let fill = env.pop(FunctionArg(1))?;
-                let f = env.pop(FunctionArg(2))?;
-                env.with_fill(fill, |env| env.call(f))?;
-            }
             Primitive::Bind => {
                 // This is only run if bind was terminated with | and not optimized out
                 let f = env.pop(FunctionArg(1))?;
@@ -494,38 +698,31 @@ impl Primitive {
                     )),
                 }
             }
-            Primitive::Both => fork::both(env)?,
-            Primitive::Fork => fork::fork(env)?,
-            Primitive::Bracket => fork::bracket(env)?,
-            Primitive::If => fork::iff(env)?,
-            Primitive::Try => {
-                let f = env.pop(FunctionArg(1))?;
-                let handler = env.pop(FunctionArg(2))?;
-                let f_args = f.signature().args;
-                let backup = env.clone_stack_top(f_args);
-                let bottom = env.stack_size().saturating_sub(f_args);
-                if let Err(e) = env.call(f) {
-                    env.truncate_stack(bottom);
-                    env.backend.save_error_color(&e);
-                    env.push(e.value());
-                    for val in backup {
-                        env.push(val);
-                    }
-                    env.call(handler)?;
-                }
-            }
             Primitive::Assert => {
                 let msg = env.pop(1)?;
                 let cond = env.pop(2)?;
                 if !cond.as_nat(env, "").is_ok_and(|n| n == 1) {
                     return Err(UiuaError::Throw(msg.into(), env.span().clone()));
                 }
+                env.test_results.passed += 1;
             }
-            Primitive::Rand => {
-                thread_local! {
-                    static RNG: RefCell<SmallRng> = RefCell::new(SmallRng::seed_from_u64(instant::now().to_bits()));
+            Primitive::AssertShape => {
+                let shape = env
+                    .pop(1)?
+                    .as_naturals(env, "Shape must be a list of natural numbers")?;
+                let value = env.pop(2)?;
+                if value.shape() != shape {
+                    return Err(env.error(format!(
+                        "Expected an array of shape {}, but its shape is {}",
+                        FormatShape(&shape),
+                        FormatShape(value.shape())
+                    )));
                 }
-                env.push(RNG.with(|rng| rng.borrow_mut().gen::<f64>()));
+                env.push(value);
+            }
+            Primitive::Rand => {
+                let val: f64 = env.rng.gen();
+                env.push(val);
             }
             Primitive::Gen => {
                 let seed = env.pop(1)?;
@@ -563,13 +760,10 @@ impl Primitive {
                     Value::Num(_) | Value::Byte(_) => 0,
                     Value::Char(_) => 1,
                     Value::Func(_) => 2,
+                    Value::Complex(_) => 3,
+                    Value::Rational(_) => 4,
                 });
             }
-            Primitive::Spawn => {
-                let f = env.pop("thread function")?;
-                let handle = env.spawn(f.signature().args, |env| env.call(f))?;
-                env.push(handle);
-            }
             Primitive::Wait => {
                 let handle = env.pop(1)?;
                 env.wait(handle)?;
@@ -577,7 +771,7 @@ impl Primitive {
             Primitive::Trace => trace(env, false)?,
             Primitive::InvTrace => trace(env, true)?,
             Primitive::Dump => dump(env),
-            Primitive::Sys(io) => io.run(env)?,
+            prim => unreachable!("{prim:?} calls functions, so it is run by Primitive::run"),
         }
         Ok(())
     }
@@ -964,6 +1158,60 @@ mod tests {
         }
     }
 
+    #[test]
+    fn glyph_round_trip() {
+        for prim in Primitive::all() {
+            if let Some(c) = prim.unicode() {
+                assert_eq!(Primitive::from_unicode(c), Some(prim));
+            }
+            if let Some(ascii) = prim.ascii() {
+                assert_eq!(Primitive::from_ascii(&ascii.to_string()), Some(prim));
+            }
+        }
+        assert_eq!(Primitive::from_ascii("!="), Some(Primitive::Ne));
+        assert_eq!(Primitive::from_unicode('a'), None);
+        assert_eq!(Primitive::from_ascii("a"), None);
+    }
+
+    #[test]
+    fn constants() {
+        use std::f64::consts::*;
+        for (input, expected) in [
+            ("π", PI),
+            ("τ", TAU),
+            ("η", FRAC_PI_2),
+            ("∞", f64::INFINITY),
+            ("e", E),
+        ] {
+            let mut env = Uiua::with_native_sys();
+            env.load_str(input).unwrap();
+            assert_eq!(env.take_stack(), [Value::from(expected)], "{input}");
+        }
+        let formatted = crate::format::format_str("pi tau eta inf", &Default::default()).unwrap();
+        assert_eq!(formatted.output.trim_end(), "π τ η ∞");
+    }
+
+    #[test]
+    fn reduce_empty_identity() {
+        assert_eq!(Primitive::Add.identity_value(), Some(Value::from(0.0)));
+        assert_eq!(Primitive::Sub.identity_value(), None);
+        for (input, expected) in [
+            ("/+ []", 0.0),
+            ("/× []", 1.0),
+            ("/↥ []", f64::NEG_INFINITY),
+            ("/↧ []", f64::INFINITY),
+            ("/↥ \"\"", f64::NEG_INFINITY),
+            ("/bitor \"\"", 0.0),
+            ("/and []", 1.0),
+            ("/or []", 0.0),
+        ] {
+            let mut env = Uiua::with_native_sys();
+            env.load_str(input).unwrap();
+            assert_eq!(env.take_stack(), [Value::from(expected)], "{input}");
+        }
+        assert!(Uiua::with_native_sys().load_str("/- \"\"").is_err());
+    }
+
     #[test]
     fn primitive_from_name() {
         assert_eq!(Primitive::from_format_name("rev"), Some(Primitive::Reverse));
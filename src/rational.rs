@@ -0,0 +1,216 @@
+//! Exact rational numbers
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+/// Uiua's rational number type
+///
+/// Rationals are always stored in lowest terms with a positive denominator.
+/// A result that does not fit in `i64`s, or a division by zero, is `NaN`,
+/// which is stored as `0/0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "RawRational")
+)]
+pub struct Rational {
+    num: i64,
+    den: i64,
+}
+
+/// A rational number as it is serialized, which may not be in lowest terms
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct RawRational {
+    num: i64,
+    den: i64,
+}
+
+#[cfg(feature = "serde")]
+impl From<RawRational> for Rational {
+    fn from(raw: RawRational) -> Self {
+        Self::new(raw.num, raw.den)
+    }
+}
+
+impl Rational {
+    /// The number `0`
+    pub const ZERO: Self = Self { num: 0, den: 1 };
+    /// A result that is not a rational number
+    pub const NAN: Self = Self { num: 0, den: 0 };
+    /// Make a rational number from a numerator and denominator
+    pub fn new(num: i64, den: i64) -> Self {
+        Self::reduce(num.into(), den.into())
+    }
+    fn reduce(num: i128, den: i128) -> Self {
+        if den == 0 {
+            return Self::NAN;
+        }
+        let sign = if den < 0 { -1 } else { 1 };
+        let gcd = gcd(num, den);
+        match (
+            i64::try_from(sign * num / gcd),
+            i64::try_from(sign * den / gcd),
+        ) {
+            (Ok(num), Ok(den)) => Self { num, den },
+            _ => Self::NAN,
+        }
+    }
+    /// The numerator
+    pub fn numer(self) -> i64 {
+        self.num
+    }
+    /// The denominator
+    pub fn denom(self) -> i64 {
+        self.den
+    }
+    /// Check if the number is `NaN`
+    pub fn is_nan(self) -> bool {
+        self.den == 0
+    }
+    /// Convert the number to a float
+    pub fn to_f64(self) -> f64 {
+        if self.is_nan() {
+            f64::NAN
+        } else {
+            self.num as f64 / self.den as f64
+        }
+    }
+    /// The absolute value
+    pub fn abs(self) -> Self {
+        Self::reduce(i128::from(self.num).abs(), self.den.into())
+    }
+    /// `-1`, `0`, or `1` depending on the sign
+    pub fn signum(self) -> Self {
+        if self.is_nan() {
+            self
+        } else {
+            Self::new(self.num.signum(), 1)
+        }
+    }
+}
+
+fn gcd(mut a: i128, mut b: i128) -> i128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.abs().max(1)
+}
+
+impl From<i64> for Rational {
+    fn from(num: i64) -> Self {
+        Self::new(num, 1)
+    }
+}
+
+impl From<u8> for Rational {
+    fn from(num: u8) -> Self {
+        Self::new(num.into(), 1)
+    }
+}
+
+impl From<Rational> for f64 {
+    fn from(r: Rational) -> Self {
+        r.to_f64()
+    }
+}
+
+impl Add for Rational {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::reduce(
+            self.num as i128 * rhs.den as i128 + rhs.num as i128 * self.den as i128,
+            self.den as i128 * rhs.den as i128,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self::reduce(
+            self.num as i128 * rhs.num as i128,
+            self.den as i128 * rhs.den as i128,
+        )
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        Self::reduce(
+            self.num as i128 * rhs.den as i128,
+            self.den as i128 * rhs.num as i128,
+        )
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::reduce(-(self.num as i128), self.den.into())
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_nan(), other.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => {
+                (self.num as i128 * other.den as i128).cmp(&(other.num as i128 * self.den as i128))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_nan() {
+            write!(f, "NaN")
+        } else if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[test]
+fn rational_arithmetic() {
+    let third = Rational::new(1, 3);
+    let sixth = Rational::new(1, 6);
+    assert_eq!(third + sixth, Rational::new(1, 2));
+    assert_eq!(third - sixth, sixth);
+    assert_eq!(third * sixth, Rational::new(1, 18));
+    assert_eq!(third / sixth, Rational::from(2i64));
+    assert_eq!(Rational::new(2, -4), Rational::new(-1, 2));
+    assert!((third / Rational::ZERO).is_nan());
+    assert!((Rational::from(i64::MAX) + Rational::from(1i64)).is_nan());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn rational_deserialize() {
+    let parse = |json| serde_json::from_str::<Rational>(json).unwrap();
+    assert_eq!(parse(r#"{"num":2,"den":-4}"#), Rational::new(-1, 2));
+    assert!(parse(r#"{"num":1,"den":0}"#).is_nan());
+}
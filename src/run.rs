@@ -6,18 +6,23 @@ use std::{
     panic::{catch_unwind, AssertUnwindSafe},
     path::{Path, PathBuf},
     str::FromStr,
-    sync::Arc,
+    sync::{
+        atomic::{self, AtomicBool},
+        Arc,
+    },
 };
 
 use instant::Duration;
 use parking_lot::Mutex;
+use rand::{rngs::SmallRng, Rng, SeedableRng};
 
 use crate::{
     array::Array,
     function::*,
-    lex::Span,
+    lex::{CodeSpan, Span},
     parse::parse,
     primitive::{Primitive, CONSTANTS},
+    stack,
     value::Value,
     Diagnostic, DiagnosticKind, Handle, Ident, NativeSys, SysBackend, TraceFrame, UiuaError,
     UiuaResult,
@@ -60,6 +65,87 @@ pub struct Uiua {
     cli_file_path: PathBuf,
     /// The system backend
     pub(crate) backend: Arc<dyn SysBackend>,
+    /// The number of instructions executed by this thread
+    instructions_executed: u64,
+    /// A limit on the number of primitives run
+    op_limit: Option<u64>,
+    /// A limit on the number of elements in created arrays
+    array_limit: Option<usize>,
+    /// The number of primitives run since loading started
+    ops_executed: u64,
+    /// A callback for reporting progress and how often to call it
+    progress: Option<(u64, ProgressFn)>,
+    /// The random number generator used by `random`
+    pub(crate) rng: SmallRng,
+    /// A flag that can be set to stop execution
+    cancelled: Arc<AtomicBool>,
+    /// Whether programs may write to the filesystem
+    pub(crate) allow_file_writes: bool,
+    /// The results of assertions run in test mode
+    pub(crate) test_results: TestResults,
+    /// The maximum depth of nested function calls
+    max_call_depth: usize,
+    /// Time spent in each primitive, if profiling is enabled
+    profiler: Option<Profiler>,
+}
+
+/// The default maximum depth of nested function calls
+///
+/// See [`Uiua::with_max_call_depth`]
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 1000;
+
+type ProgressFn = Arc<dyn Fn(Progress) + Send + Sync>;
+
+/// Information about a running program, passed to a progress callback
+///
+/// See [`Uiua::with_progress_callback`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// The number of instructions executed so far
+    pub instructions: u64,
+    /// The current height of the stack
+    pub stack_size: usize,
+}
+
+/// The results of the assertions in a file run in [`RunMode::Test`]
+///
+/// See [`Uiua::take_test_results`]
+#[derive(Debug, Clone, Default)]
+pub struct TestResults {
+    /// The number of assertions that passed
+    pub passed: usize,
+    /// The errors from assertions that failed
+    pub failures: Vec<UiuaError>,
+}
+
+/// The time spent running a primitive
+///
+/// See [`Uiua::profile_primitives`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PrimitiveProfile {
+    /// The number of times the primitive was run
+    pub calls: u64,
+    /// The time spent in milliseconds, not including time spent in primitives it ran
+    pub time: f64,
+}
+
+#[derive(Clone, Default)]
+struct Profiler {
+    prims: HashMap<Primitive, PrimitiveProfile>,
+    /// The time spent in nested primitives by each primitive that is currently running
+    nested: Vec<f64>,
+}
+
+impl Profiler {
+    fn record(&mut self, prim: Primitive, elapsed: f64) {
+        let nested = self.nested.pop().unwrap_or(0.0);
+        let profile = self.prims.entry(prim).or_default();
+        profile.calls += 1;
+        profile.time += elapsed - nested;
+        if let Some(parent) = self.nested.last_mut() {
+            *parent += elapsed;
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -175,6 +261,17 @@ impl Uiua {
             cli_file_path: PathBuf::new(),
             execution_limit: None,
             execution_start: 0.0,
+            instructions_executed: 0,
+            op_limit: None,
+            array_limit: None,
+            ops_executed: 0,
+            progress: None,
+            rng: SmallRng::seed_from_u64(instant::now().to_bits()),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            allow_file_writes: false,
+            test_results: TestResults::default(),
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            profiler: None,
         }
     }
     /// Create a new Uiua runtime with a custom IO backend
@@ -199,6 +296,99 @@ impl Uiua {
         self.execution_limit = Some(limit.as_millis() as f64);
         self
     }
+    /// Limit the number of primitives that can be run
+    ///
+    /// Exceeding the limit is an error. This bounds programs that would otherwise
+    /// run forever, independently of how fast the machine is.
+    /// The count starts over each time code is loaded.
+    /// Threads spawned by the program each get the same limit.
+    pub fn with_op_limit(mut self, limit: u64) -> Self {
+        self.op_limit = Some(limit);
+        self
+    }
+    /// Limit the number of elements in arrays created by [`Primitive::Range`],
+    /// [`Primitive::Reshape`], and [`Primitive::Table`]
+    ///
+    /// Creating a larger array is an error rather than an attempt to allocate it.
+    pub fn with_array_limit(mut self, limit: usize) -> Self {
+        self.array_limit = Some(limit);
+        self
+    }
+    /// Error if an array with `len` elements would exceed the array limit
+    ///
+    /// A `len` of `None` means the element count does not fit in a `usize`.
+    pub(crate) fn check_array_len(&self, len: Option<usize>) -> UiuaResult<usize> {
+        match (len, self.array_limit) {
+            (Some(len), Some(limit)) if len > limit => Err(self.error(format!(
+                "Creating an array with {len} elements would exceed the limit of {limit}"
+            ))),
+            (Some(len), _) => Ok(len),
+            (None, _) => Err(self.error("Array would have too many elements")),
+        }
+    }
+    /// Register a callback that is called every `interval` instructions
+    ///
+    /// This is useful for showing progress or keeping a UI responsive during long runs.
+    pub fn with_progress_callback(
+        mut self,
+        interval: u64,
+        f: impl Fn(Progress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some((interval.max(1), Arc::new(f)));
+        self
+    }
+    /// Get a handle that can be used to cancel execution
+    ///
+    /// Setting the flag to `true`, possibly from another thread, will make the runtime
+    /// stop with [`UiuaError::Cancelled`] at the next instruction. The flag is shared
+    /// with any threads spawned by the program.
+    pub fn cancel_handle(&self) -> Arc<AtomicBool> {
+        self.cancelled.clone()
+    }
+    /// Seed the random number generator
+    ///
+    /// Runtimes with the same seed will generate the same sequence of random numbers.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = SmallRng::seed_from_u64(seed);
+        self
+    }
+    /// Limit how deeply function calls can be nested
+    ///
+    /// Exceeding the limit, for example with unbounded [`Primitive::Recur`], is an error.
+    /// Default is [`DEFAULT_MAX_CALL_DEPTH`]
+    ///
+    /// Calls that would overflow the native stack are an error even below the limit.
+    pub fn with_max_call_depth(mut self, depth: usize) -> Self {
+        self.max_call_depth = depth;
+        self
+    }
+    /// Enable or disable measuring the time spent in each primitive
+    ///
+    /// The measurements can be retrieved with [`Uiua::primitive_profile`].
+    /// Profiling is disabled by default
+    pub fn profile_primitives(mut self, profile: bool) -> Self {
+        self.profiler = profile.then(Profiler::default);
+        self
+    }
+    /// Get the time spent in each primitive that has run, sorted from most to least time
+    ///
+    /// This is empty unless [`Uiua::profile_primitives`] is enabled.
+    /// Primitives run in spawned threads are not included.
+    pub fn primitive_profile(&self) -> Vec<(Primitive, PrimitiveProfile)> {
+        let Some(profiler) = &self.profiler else {
+            return Vec::new();
+        };
+        let mut profile: Vec<_> = profiler.prims.iter().map(|(p, t)| (*p, *t)).collect();
+        profile.sort_by(|(_, a), (_, b)| b.time.total_cmp(&a.time));
+        profile
+    }
+    /// Allow or disallow writing to the filesystem
+    ///
+    /// Writing is disallowed by default
+    pub fn allow_file_writes(mut self, allow: bool) -> Self {
+        self.allow_file_writes = allow;
+        self
+    }
     /// Set the [`RunMode`]
     ///
     /// Default is [`RunMode::Normal`]
@@ -232,14 +422,16 @@ impl Uiua {
     pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> UiuaResult {
         let path = path.as_ref();
         let input = fs::read_to_string(path).map_err(|e| UiuaError::Load(path.into(), e.into()))?;
-        self.load_impl(&input, Some(path))
+        self.load_str_path(&input, path)
     }
     /// Load a Uiua file from a string
     pub fn load_str(&mut self, input: &str) -> UiuaResult {
+        self.ops_executed = 0;
         self.load_impl(input, None)
     }
     /// Load a Uiua file from a string with a path for error reporting
     pub fn load_str_path<P: AsRef<Path>>(&mut self, input: &str, path: P) -> UiuaResult {
+        self.ops_executed = 0;
         self.load_impl(input, Some(path.as_ref()))
     }
     /// Run in a scoped context. Names defined in this context will be removed when the scope ends.
@@ -321,6 +513,24 @@ code:
             }
         }
     }
+    /// Resolve an import path relative to the directory of the importing file
+    ///
+    /// Falls back to the path as given if there is no such file there.
+    pub(crate) fn resolve_import_path(&self, path: &str) -> String {
+        if let Span::Code(CodeSpan {
+            path: Some(importer),
+            ..
+        }) = self.span()
+        {
+            if let Some(dir) = importer.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+                let resolved = dir.join(path).to_string_lossy().into_owned();
+                if self.backend.file_exists(&resolved) {
+                    return resolved;
+                }
+            }
+        }
+        path.into()
+    }
     pub(crate) fn import(&mut self, input: &str, path: &Path) -> UiuaResult {
         if self.current_imports.lock().contains(path) {
             return Err(self.error(format!(
@@ -329,7 +539,8 @@ code:
             )));
         }
         if !self.imports.lock().contains_key(path) {
-            let import = self.in_scope(false, |env| env.load_str_path(input, path).map(drop))?;
+            // Imports count toward the op limit of the importing program
+            let import = self.in_scope(false, |env| env.load_impl(input, Some(path)))?;
             self.imports.lock().insert(path.into(), import);
         }
         self.stack.extend(self.imports.lock()[path].iter().cloned());
@@ -346,6 +557,15 @@ code:
     }
     fn exec(&mut self, frame: StackFrame) -> UiuaResult {
         let ret_height = self.scope.call.len();
+        if ret_height > self.max_call_depth {
+            return Err(self.error(format!(
+                "Maximum call depth of {} exceeded",
+                self.max_call_depth
+            )));
+        }
+        if stack::remaining() < stack::RED_ZONE {
+            return Err(self.error("Stack overflow: function calls are nested too deeply"));
+        }
         self.scope.call.push(frame);
         while self.scope.call.len() > ret_height {
             let frame = self.scope.call.last().unwrap();
@@ -397,12 +617,31 @@ code:
                     self.push(val);
                     Ok(())
                 })(),
-                &Instr::Prim(prim, span) => (|| {
-                    self.push_span(span, Some(prim));
-                    prim.run(self)?;
-                    self.pop_span();
-                    Ok(())
-                })(),
+                &Instr::Prim(prim, span) => {
+                    let start = self.profiler.as_mut().map(|profiler| {
+                        profiler.nested.push(0.0);
+                        instant::now()
+                    });
+                    let res = (|| {
+                        self.push_span(span, Some(prim));
+                        if let Some(limit) = self.op_limit {
+                            if self.ops_executed >= limit {
+                                return Err(
+                                    self.error(format!("Operation limit of {limit} exceeded"))
+                                );
+                            }
+                        }
+                        self.ops_executed += 1;
+                        self.check_prim_args(prim)?;
+                        prim.run(self)?;
+                        self.pop_span();
+                        Ok(())
+                    })();
+                    if let (Some(start), Some(profiler)) = (start, &mut self.profiler) {
+                        profiler.record(prim, instant::now() - start);
+                    }
+                    res
+                }
                 &Instr::Call(span) => self
                     .pop("called function")
                     .and_then(|f| self.call_with_span(f, span)),
@@ -471,6 +710,18 @@ code:
             } else {
                 // Go to next instruction
                 self.scope.call.last_mut().unwrap().pc += 1;
+                self.instructions_executed += 1;
+                if let Some((interval, f)) = &self.progress {
+                    if self.instructions_executed.is_multiple_of(*interval) {
+                        f(Progress {
+                            instructions: self.instructions_executed,
+                            stack_size: self.stack.len(),
+                        });
+                    }
+                }
+                if self.cancelled.load(atomic::Ordering::Relaxed) {
+                    return Err(UiuaError::Cancelled(self.span()));
+                }
                 if let Some(limit) = self.execution_limit {
                     if instant::now() - self.execution_start > limit {
                         return Err(UiuaError::Timeout(self.span()));
@@ -586,6 +837,26 @@ code:
         }
         res
     }
+    /// Make sure there are enough values on the stack for a primitive
+    fn check_prim_args(&self, prim: Primitive) -> UiuaResult {
+        // Identity is allowed to run on an empty stack
+        if prim == Primitive::Identity {
+            return Ok(());
+        }
+        let Some(args) = prim.args() else {
+            return Ok(());
+        };
+        let expected = args as usize + prim.modifier_args().unwrap_or(0) as usize;
+        if self.stack.len() < expected {
+            let name = prim.name().map_or_else(|| prim.to_string(), Into::into);
+            return Err(self.error(format!(
+                "`{name}` expected {expected} value{} but the stack had {}",
+                if expected == 1 { "" } else { "s" },
+                self.stack.len()
+            )));
+        }
+        Ok(())
+    }
     /// Simulates popping a value and imediately pushing it back
     pub(crate) fn touch_array_stack(&mut self) {
         for bottom in &mut self.scope.array {
@@ -617,6 +888,10 @@ code:
     pub fn take_diagnostics(&mut self) -> BTreeSet<Diagnostic> {
         take(&mut self.diagnostics)
     }
+    /// Take the results of the assertions run in [`RunMode::Test`]
+    pub fn take_test_results(&mut self) -> TestResults {
+        take(&mut self.test_results)
+    }
     pub fn clone_stack_top(&self, n: usize) -> Vec<Value> {
         self.stack.iter().rev().take(n).rev().cloned().collect()
     }
@@ -719,6 +994,8 @@ code:
                     set = true;
                 }
             }
+            Value::Complex(_) => return Err(self.error("Fill values cannot be complex")),
+            Value::Rational(_) => return Err(self.error("Fill values cannot be rational")),
             Value::Char(c) => {
                 if let Some(&c) = c.as_scalar() {
                     self.scope.fills.chars.push(c);
@@ -743,6 +1020,7 @@ code:
             Value::Num(_) | Value::Byte(_) => {
                 self.scope.fills.nums.pop();
             }
+            Value::Complex(_) | Value::Rational(_) => {}
             Value::Char(_) => {
                 self.scope.fills.chars.pop();
             }
@@ -786,6 +1064,17 @@ code:
             backend: self.backend.clone(),
             execution_limit: self.execution_limit,
             execution_start: self.execution_start,
+            instructions_executed: 0,
+            op_limit: self.op_limit,
+            array_limit: self.array_limit,
+            ops_executed: 0,
+            progress: self.progress.clone(),
+            rng: SmallRng::seed_from_u64(self.rng.gen()),
+            cancelled: self.cancelled.clone(),
+            allow_file_writes: self.allow_file_writes,
+            test_results: TestResults::default(),
+            max_call_depth: self.max_call_depth,
+            profiler: None,
         };
         self.backend
             .spawn(env, Box::new(f))
@@ -899,3 +1188,259 @@ where
         format!("function {}'s {}", self.0, self.1.arg_name())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UiuaErrorKind;
+
+    #[test]
+    fn call_depth_limit() {
+        let mut env = Uiua::with_native_sys().with_max_call_depth(5);
+        env.load_str("!(|1 ×↬>2.-1.) 4").unwrap();
+        assert_eq!(env.take_stack(), [Value::from(24.0)]);
+        let error = env.load_str("!(|1 ↬1) 0").unwrap_err();
+        assert!(error.message().contains("Maximum call depth of 5 exceeded"));
+    }
+
+    #[test]
+    fn op_limit_across_imports() {
+        let dir = std::env::temp_dir().join("uiua_op_limit_import_test");
+        fs::create_dir_all(&dir).unwrap();
+        for i in 0..10 {
+            fs::write(dir.join(format!("lib{i}.ua")), "⍥(+1)100 0").unwrap();
+        }
+        let code = "∵(&i $\"lib_.ua\")⇡10";
+        let main = dir.join("main.ua");
+        fs::write(&main, code).unwrap();
+        let mut env = Uiua::with_native_sys();
+        env.load_file(&main).unwrap();
+        assert_eq!(env.take_stack(), [Value::from(vec![100.0; 10])]);
+        let error = Uiua::with_native_sys()
+            .with_op_limit(500)
+            .load_file(&main)
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Operation limit of 500 exceeded"),
+            "{error}"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn unbounded_recursion_errors() {
+        let handle = std::thread::Builder::new()
+            .stack_size(256 * 1024 * 1024)
+            .spawn(|| Uiua::with_native_sys().load_str("!(|1 ↬1) 0"))
+            .unwrap();
+        let error = handle.join().unwrap().unwrap_err();
+        assert!(
+            (error.message()).contains(&format!(
+                "Maximum call depth of {DEFAULT_MAX_CALL_DEPTH} exceeded"
+            )),
+            "{error}"
+        );
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
+    fn recursion_on_small_stack_errors() {
+        let handle = std::thread::Builder::new()
+            .stack_size(1024 * 1024)
+            .spawn(|| Uiua::with_native_sys().load_str("!(|1 ↬1) 0"))
+            .unwrap();
+        let error = handle.join().unwrap().unwrap_err();
+        assert!(error.message().contains("Stack overflow"), "{error}");
+    }
+
+    #[test]
+    fn deep_recursion_with_large_stack() {
+        let handle = std::thread::Builder::new()
+            .stack_size(256 * 1024 * 1024)
+            .spawn(|| {
+                let mut env = Uiua::with_native_sys();
+                env.load_str("!(|1 +1↬>0.-1) 250").map(|_| env.take_stack())
+            })
+            .unwrap();
+        assert_eq!(handle.join().unwrap().unwrap(), [Value::from(250.0)]);
+    }
+
+    #[test]
+    fn progress_callback() {
+        use std::sync::atomic::{AtomicU64, Ordering};
+        let calls = Arc::new(AtomicU64::new(0));
+        let counter = calls.clone();
+        let mut env = Uiua::with_native_sys().with_progress_callback(10, move |progress| {
+            assert_eq!(progress.instructions % 10, 0);
+            counter.fetch_add(1, Ordering::Relaxed);
+        });
+        env.load_str("⍥(+1)100 0").unwrap();
+        assert!(calls.load(Ordering::Relaxed) > 0);
+    }
+
+    #[test]
+    fn seeded_random() {
+        let run = |seed| {
+            let mut env = Uiua::with_native_sys().with_seed(seed);
+            env.load_str("[⍥⚂10]").unwrap();
+            env.take_stack()
+        };
+        assert_eq!(run(42), run(42));
+        assert_ne!(run(42), run(43));
+    }
+
+    #[test]
+    fn cancel_execution() {
+        use std::{sync::atomic::Ordering, thread, time::Duration};
+        let mut env = Uiua::with_native_sys();
+        let handle = env.cancel_handle();
+        thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            handle.store(true, Ordering::Relaxed);
+        });
+        let res = env.load_str("⍥(+1)∞ 0");
+        assert!(
+            matches!(res, Err(UiuaError::Traced { error, .. }) if matches!(*error, UiuaError::Cancelled(_)))
+        );
+    }
+
+    #[test]
+    fn eval_str() {
+        let mut env = Uiua::with_native_sys().with_mode(RunMode::Normal);
+        env.load_str("+1 2").unwrap();
+        let shown: Vec<String> = env.take_stack().iter().map(Value::show).collect();
+        assert_eq!(shown, ["3"]);
+    }
+
+    #[test]
+    fn test_mode_assertions() {
+        let code = "\
+~~~
+⍤\"one\" =1 1
+⍤\"two\" =1 2
+⍤\"three\" =2 2
+⍤\"four\" =3 4
+~~~";
+        let mut env = Uiua::with_native_sys().with_mode(RunMode::Test);
+        env.load_str(code).unwrap();
+        let results = env.take_test_results();
+        assert_eq!(results.passed, 2);
+        let messages: Vec<String> = (results.failures.into_iter())
+            .map(|failure| failure.value().to_string())
+            .collect();
+        assert_eq!(messages, ["two", "four"]);
+
+        let mut env = Uiua::with_native_sys().with_mode(RunMode::All);
+        let error = env.load_str(code).unwrap_err();
+        assert_eq!(error.kind(), UiuaErrorKind::Throw);
+    }
+
+    #[test]
+    fn stack_underflow_message() {
+        for (code, found) in [("+1", 1), ("+", 0)] {
+            let error = Uiua::with_native_sys().load_str(code).unwrap_err();
+            let message = error.message();
+            let expected = format!("`add` expected 2 values but the stack had {found}");
+            assert!(message.contains(&expected), "{message}");
+        }
+    }
+
+    #[test]
+    fn load_str_path_in_errors() {
+        let mut env = Uiua::with_native_sys();
+        let error = env.load_str_path("+1", "calc.ua").unwrap_err();
+        let Some(Span::Code(span)) = error.span() else {
+            panic!("error has no code span: {error}");
+        };
+        assert_eq!(span.path.as_deref(), Some(std::path::Path::new("calc.ua")));
+        let shown = error.show(false);
+        assert!(shown.contains("calc.ua"), "path not shown:\n{shown}");
+        assert!(shown.contains("+1"), "source not shown:\n{shown}");
+    }
+
+    #[test]
+    fn push_and_pop() {
+        let mut env = Uiua::with_native_sys();
+        env.push(10.0);
+        env.push(vec![1.0, 2.0, 3.0]);
+        env.load_str("/+").unwrap();
+        let sum = env.pop(1).unwrap();
+        assert_eq!(sum, Value::from(6.0));
+        assert_eq!(env.take_stack(), [Value::from(10.0)]);
+        assert!(env.pop(1).is_err());
+    }
+
+    #[test]
+    fn import_relative_to_file() {
+        let dir = std::env::temp_dir().join("uiua_import_test");
+        let sub = dir.join("sub");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::write(sub.join("lib.ua"), "Double ← ×2\nHalf ← ÷2\nDouble_Half\n").unwrap();
+        std::fs::write(
+            sub.join("main.ua"),
+            "lib ← &i \"lib.ua\"\nDouble ← use \"Double\" lib\nDouble 5\n&i \"lib.ua\"\n",
+        )
+        .unwrap();
+        let mut env = Uiua::with_native_sys();
+        env.load_file(sub.join("main.ua")).unwrap();
+        assert_eq!(env.pop(1).unwrap().type_name(), "function");
+        assert_eq!(env.pop(1).unwrap(), Value::from(10.0));
+
+        std::fs::write(dir.join("a.ua"), "&i \"b.ua\"\n").unwrap();
+        std::fs::write(dir.join("b.ua"), "&i \"a.ua\"\n").unwrap();
+        let error = Uiua::with_native_sys()
+            .load_file(dir.join("a.ua"))
+            .unwrap_err();
+        assert!(
+            error.message().contains("Cycle detected importing"),
+            "{error}"
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn primitive_profile() {
+        let mut env = Uiua::with_native_sys().profile_primitives(true);
+        env.load_str("+.+.+.+.+.+.+.+.+.+. ⇡100000").unwrap();
+        let profile = env.primitive_profile();
+        assert_eq!(profile[0].0, Primitive::Add, "{profile:?}");
+        assert_eq!(profile[0].1.calls, 10);
+        assert!(Uiua::with_native_sys().primitive_profile().is_empty());
+    }
+
+    #[test]
+    fn op_limit() {
+        let mut env = Uiua::with_native_sys().with_op_limit(1000);
+        let err = env.load_str("⍥(+1)∞ 0").unwrap_err();
+        assert!(
+            err.to_string().contains("Operation limit of 1000 exceeded"),
+            "{err}"
+        );
+        let mut env = Uiua::with_native_sys().with_op_limit(1000);
+        env.load_str("⍥(+1)100 0").unwrap();
+        assert_eq!(env.take_stack(), [Value::from(100.0)]);
+    }
+
+    #[test]
+    fn array_limit() {
+        for input in [
+            "⇡1000000",
+            "⇡100_100",
+            "↯1000_1000 0",
+            "↯1000 [1 2]",
+            "⊞+.⇡100",
+        ] {
+            let mut env = Uiua::with_native_sys().with_array_limit(1000);
+            let err = env.load_str(input).unwrap_err();
+            assert!(
+                err.to_string().contains("exceed the limit of 1000"),
+                "{input}: {err}"
+            );
+        }
+        let mut env = Uiua::with_native_sys().with_array_limit(1000);
+        env.load_str("⇡1000 ↯10_10 0 ⊞+.⇡10").unwrap();
+        assert_eq!(env.take_stack().len(), 3);
+    }
+}
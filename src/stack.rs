@@ -0,0 +1,76 @@
+//! Measuring how much of the native stack is left
+//!
+//! Deep recursion in Uiua code recurses in the interpreter as well.
+//! The call depth limit keeps this in check, but a thread with a small stack
+//! can still run out first, so the interpreter also checks the remaining stack
+//! before each call where the platform can report it.
+
+use std::cell::Cell;
+
+/// The amount of stack that must remain for a function to be called
+///
+/// This must be more than a single nested call and a primitive use,
+/// which is much more in unoptimized builds.
+pub(crate) const RED_ZONE: usize = if cfg!(debug_assertions) {
+    256 * 1024
+} else {
+    64 * 1024
+};
+
+thread_local! {
+    /// The lowest address of the current thread's stack, if it is known
+    static STACK_LIMIT: Cell<Option<Option<usize>>> = const { Cell::new(None) };
+}
+
+/// Get the number of bytes left on the current thread's stack
+///
+/// Returns `usize::MAX` if the platform cannot report it.
+pub(crate) fn remaining() -> usize {
+    let limit = STACK_LIMIT.with(|limit| {
+        limit.get().unwrap_or_else(|| {
+            let new = stack_limit();
+            limit.set(Some(new));
+            new
+        })
+    });
+    match limit {
+        Some(limit) => current_address().saturating_sub(limit),
+        None => usize::MAX,
+    }
+}
+
+#[inline(never)]
+fn current_address() -> usize {
+    let marker = 0u8;
+    std::hint::black_box(&marker) as *const u8 as usize
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn stack_limit() -> Option<usize> {
+    use std::mem::MaybeUninit;
+    unsafe {
+        let mut attr = MaybeUninit::<libc::pthread_attr_t>::uninit();
+        if libc::pthread_getattr_np(libc::pthread_self(), attr.as_mut_ptr()) != 0 {
+            return None;
+        }
+        let mut addr = std::ptr::null_mut();
+        let mut size = 0;
+        let res = libc::pthread_attr_getstack(attr.as_ptr(), &mut addr, &mut size);
+        libc::pthread_attr_destroy(attr.as_mut_ptr());
+        (res == 0).then_some(addr as usize)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn stack_limit() -> Option<usize> {
+    unsafe {
+        let thread = libc::pthread_self();
+        let top = libc::pthread_get_stackaddr_np(thread) as usize;
+        Some(top - libc::pthread_get_stacksize_np(thread))
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos")))]
+fn stack_limit() -> Option<usize> {
+    None
+}
@@ -154,6 +154,7 @@ sys_op! {
     (2(0), Write, "&w", "write"),
     /// Run the code from a file in a scope
     ///
+    /// A relative path is resolved from the directory of the importing file, or from the working directory if there is no such file there.
     /// If the file has already been imported, its code will not be run again, but the values it originally pushed onto the stack will be pushed again.
     /// Functions can be extracted from the imported modules with [use].
     /// ex: ex ← &i "example.ua"
@@ -366,6 +367,10 @@ pub trait SysBackend: Any + Send + Sync + 'static {
     fn term_size(&self) -> Result<(usize, usize), String> {
         Err("Getting the terminal size is not supported in this environment".into())
     }
+    /// Get the current time in seconds since the Unix epoch
+    fn now(&self) -> f64 {
+        instant::now() / 1000.0
+    }
     fn file_exists(&self, path: &str) -> bool {
         false
     }
@@ -1127,6 +1132,7 @@ impl SysOp {
                 env.push(handle);
             }
             SysOp::FCreate => {
+                require_file_writes(env)?;
                 let path = env.pop(1)?.as_string(env, "Path must be a string")?;
                 let handle = env.backend.create_file(&path).map_err(|e| env.error(e))?;
                 env.push(handle.0 as f64);
@@ -1249,6 +1255,12 @@ impl SysOp {
                     Value::Num(arr) => arr.data.iter().map(|&x| x as u8).collect(),
                     Value::Byte(arr) => arr.data.into(),
                     Value::Char(arr) => arr.data.iter().collect::<String>().into(),
+                    Value::Complex(_) => {
+                        return Err(env.error("Cannot write complex array to file"))
+                    }
+                    Value::Rational(_) => {
+                        return Err(env.error("Cannot write rational array to file"))
+                    }
                     Value::Func(_) => return Err(env.error("Cannot write function array to file")),
                 };
                 match handle {
@@ -1300,12 +1312,19 @@ impl SysOp {
                 env.push(Array::<u8>::from_iter(bytes));
             }
             SysOp::FWriteAll => {
+                require_file_writes(env)?;
                 let path = env.pop(1)?.as_string(env, "Path must be a string")?;
                 let data = env.pop(2)?;
                 let bytes: Vec<u8> = match data {
                     Value::Num(arr) => arr.data.iter().map(|&x| x as u8).collect(),
                     Value::Byte(arr) => arr.data.into(),
                     Value::Char(arr) => arr.data.iter().collect::<String>().into(),
+                    Value::Complex(_) => {
+                        return Err(env.error("Cannot write complex array to file"))
+                    }
+                    Value::Rational(_) => {
+                        return Err(env.error("Cannot write rational array to file"))
+                    }
                     Value::Func(_) => return Err(env.error("Cannot write function array to file")),
                 };
                 env.backend
@@ -1338,6 +1357,7 @@ impl SysOp {
             }
             SysOp::Import => {
                 let path = env.pop(1)?.as_string(env, "Import path must be a string")?;
+                let path = env.resolve_import_path(&path);
                 let input = String::from_utf8(
                     env.backend
                         .file_read_all(&path)
@@ -1353,7 +1373,7 @@ impl SysOp {
                 .map_err(|e| env.error(format!("Failed to read file: {e}")))?;
                 env.import(&input, path.as_ref())?;
             }
-            SysOp::Now => env.push(instant::now() / 1000.0),
+            SysOp::Now => env.push(env.backend.now()),
             SysOp::ImDecode => {
                 let bytes = match env.pop(1)? {
                     Value::Byte(arr) => {
@@ -1622,6 +1642,14 @@ impl SysOp {
     }
 }
 
+fn require_file_writes(env: &Uiua) -> UiuaResult {
+    if env.allow_file_writes {
+        Ok(())
+    } else {
+        Err(env.error("Writing files is not allowed in this environment"))
+    }
+}
+
 fn value_to_command(value: &Value, env: &Uiua) -> UiuaResult<(String, Vec<String>)> {
     let mut strings = Vec::new();
     match value {
@@ -1668,7 +1696,7 @@ fn value_to_command(value: &Value, env: &Uiua) -> UiuaResult<(String, Vec<String
                 )))
             }
         },
-        Value::Num(_) | Value::Byte(_) => {
+        Value::Num(_) | Value::Byte(_) | Value::Complex(_) | Value::Rational(_) => {
             return Err(env.error(format!(
                 "Command must be a string or function array, but it is {}s",
                 value.type_name()
@@ -1962,3 +1990,135 @@ pub fn value_to_gif_bytes(value: &Value, frame_delay: f64) -> Result<Vec<u8>, St
     drop(encoder);
     Ok(bytes.into_inner())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+
+    /// A backend that replays canned input and records output
+    #[derive(Default)]
+    struct TestBackend {
+        /// The time returned by the next call to `now`, and how much it advances each call
+        clock: Mutex<(f64, f64)>,
+        stdin: Mutex<VecDeque<String>>,
+        stdout: Mutex<String>,
+        trace: Mutex<String>,
+    }
+
+    impl SysBackend for TestBackend {
+        fn any(&self) -> &dyn Any {
+            self
+        }
+        fn now(&self) -> f64 {
+            let mut clock = self.clock.lock();
+            let now = clock.0;
+            clock.0 += clock.1;
+            now
+        }
+        fn scan_line_stdin(&self) -> Result<Option<String>, String> {
+            Ok(self.stdin.lock().pop_front())
+        }
+        fn print_str_stdout(&self, s: &str) -> Result<(), String> {
+            self.stdout.lock().push_str(s);
+            Ok(())
+        }
+        fn print_str_trace(&self, s: &str) {
+            self.trace.lock().push_str(s);
+        }
+    }
+
+    #[test]
+    fn cli_args() {
+        let mut env = Uiua::with_native_sys().with_args(vec!["a".into(), "b".into()]);
+        env.load_str("⊔↘1 &args").unwrap();
+        let args = env.take_stack().pop().unwrap();
+        let args: Vec<String> = args.into_rows().map(|arg| arg.to_string()).collect();
+        assert_eq!(args, ["a", "b"]);
+    }
+
+    #[test]
+    fn read_file() {
+        let path = std::env::temp_dir().join("uiua_read_file_test.txt");
+        std::fs::write(&path, "hello\nworld").unwrap();
+        let mut env = Uiua::with_native_sys();
+        env.load_str(&format!("&fras {:?}", path.to_string_lossy()))
+            .unwrap();
+        let contents = env.take_stack().pop().unwrap();
+        assert_eq!(contents.to_string(), "hello\nworld");
+        std::fs::remove_file(&path).unwrap();
+        assert!(env
+            .load_str(&format!("&fras {:?}", path.to_string_lossy()))
+            .is_err());
+    }
+
+    #[test]
+    fn write_file_capability() {
+        let path = std::env::temp_dir().join("uiua_write_file_test.txt");
+        let code = format!("&fwa {:?} \"written\"", path.to_string_lossy());
+        let _ = std::fs::remove_file(&path);
+        assert!(Uiua::with_native_sys().load_str(&code).is_err());
+        assert!(!path.exists());
+        Uiua::with_native_sys()
+            .allow_file_writes(true)
+            .load_str(&code)
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "written");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn mocked_clock() {
+        let backend = TestBackend {
+            clock: Mutex::new((1000.0, 1.5)),
+            ..Default::default()
+        };
+        let mut env = Uiua::with_backend(backend);
+        env.load_str("-∶&n &n").unwrap();
+        assert_eq!(env.take_stack(), [Value::from(1.5)]);
+    }
+
+    #[test]
+    fn injected_stdin() {
+        let backend = TestBackend {
+            stdin: Mutex::new(["first".into(), "second".into()].into()),
+            ..Default::default()
+        };
+        let mut env = Uiua::with_backend(backend);
+        env.load_str("&sc &sc &sc").unwrap();
+        assert_eq!(
+            env.take_stack(),
+            [
+                Value::from("first"),
+                Value::from("second"),
+                Value::from(0u8),
+            ]
+        );
+    }
+
+    #[test]
+    fn captured_print() {
+        for (input, expected, stack_len) in [
+            (r#"&p "hi""#, "hi\n", 0),
+            (r#"&pf "hi""#, "hi", 0),
+            (r#"&p. "hi""#, "hi\n", 1),
+            ("&s [1 2]", "[1 2]\n", 0),
+        ] {
+            let mut env = Uiua::with_backend(TestBackend::default());
+            env.load_str(input).unwrap();
+            let backend = env.downcast_backend::<TestBackend>().unwrap();
+            assert_eq!(*backend.stdout.lock(), expected, "{input}");
+            assert_eq!(env.take_stack().len(), stack_len, "{input}");
+        }
+    }
+
+    #[test]
+    fn captured_trace() {
+        let mut env = Uiua::with_backend(TestBackend::default());
+        env.load_str("~[1 2 3]").unwrap();
+        assert_eq!(env.take_stack(), [Value::from(vec![1.0, 2.0, 3.0])]);
+        let backend = env.downcast_backend::<TestBackend>().unwrap();
+        assert_eq!(*backend.trace.lock(), "  1:1\n[1 2 3]\n");
+    }
+}
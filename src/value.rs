@@ -10,15 +10,19 @@ use std::{
 use crate::{
     algorithm::pervade::*,
     array::*,
+    complex::Complex,
     function::{Function, Signature},
     grid_fmt::GridFmt,
     primitive::Primitive,
+    rational::Rational,
     Uiua, UiuaResult,
 };
 
 #[derive(Clone)]
 pub enum Value {
     Num(Array<f64>),
+    Complex(Array<Complex>),
+    Rational(Array<Rational>),
     Byte(Array<u8>),
     Char(Array<char>),
     Func(Array<Arc<Function>>),
@@ -34,6 +38,8 @@ impl fmt::Debug for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::Num(array) => array.fmt(f),
+            Self::Complex(array) => array.fmt(f),
+            Self::Rational(array) => array.fmt(f),
             Self::Byte(array) => array.fmt(f),
             Self::Char(array) => array.fmt(f),
             Self::Func(array) => array.fmt(f),
@@ -42,6 +48,36 @@ impl fmt::Debug for Value {
 }
 
 impl Value {
+    /// Make a scalar number
+    pub fn scalar(num: f64) -> Self {
+        num.into()
+    }
+    /// Make a number array from a shape and its flat data
+    ///
+    /// # Panics
+    /// Panics if the product of the shape is not the length of the data
+    #[track_caller]
+    pub fn num_array(shape: &[usize], data: impl Into<Vec<f64>>) -> Self {
+        let data = data.into();
+        assert_eq!(
+            shape.iter().product::<usize>(),
+            data.len(),
+            "shape {shape:?} does not match data length {}",
+            data.len()
+        );
+        Array::new(Shape::from(shape), data).into()
+    }
+    /// Make an array from its rows, the same way as `[]` does
+    ///
+    /// # Panics
+    /// Panics if the rows do not all have the same shape
+    pub fn from_rows<V>(rows: V) -> Self
+    where
+        V: IntoIterator,
+        V::Item: Into<Value>,
+    {
+        Self::from_row_values_infallible(rows)
+    }
     pub fn signature(&self) -> Signature {
         if let Some(f) = self.as_func_array().and_then(Array::as_scalar) {
             f.signature()
@@ -91,6 +127,8 @@ impl Value {
     pub fn rows(&self) -> Box<dyn ExactSizeIterator<Item = Self> + '_> {
         match self {
             Self::Num(array) => Box::new(array.rows().map(Value::from)),
+            Self::Complex(array) => Box::new(array.rows().map(Value::from)),
+            Self::Rational(array) => Box::new(array.rows().map(Value::from)),
             Self::Byte(array) => Box::new(array.rows().map(Value::from)),
             Self::Char(array) => Box::new(array.rows().map(Value::from)),
             Self::Func(array) => Box::new(array.rows().map(Value::from)),
@@ -99,6 +137,8 @@ impl Value {
     pub fn into_rows(self) -> Box<dyn Iterator<Item = Self>> {
         match self {
             Self::Num(array) => Box::new(array.into_rows().map(Value::from)),
+            Self::Complex(array) => Box::new(array.into_rows().map(Value::from)),
+            Self::Rational(array) => Box::new(array.into_rows().map(Value::from)),
             Self::Byte(array) => Box::new(array.into_rows().map(Value::from)),
             Self::Char(array) => Box::new(array.into_rows().map(Value::from)),
             Self::Func(array) => Box::new(array.into_rows().map(Value::from)),
@@ -107,6 +147,8 @@ impl Value {
     pub fn into_rows_rev(self) -> Box<dyn Iterator<Item = Self>> {
         match self {
             Self::Num(array) => Box::new(array.into_rows_rev().map(Value::from)),
+            Self::Complex(array) => Box::new(array.into_rows_rev().map(Value::from)),
+            Self::Rational(array) => Box::new(array.into_rows_rev().map(Value::from)),
             Self::Byte(array) => Box::new(array.into_rows_rev().map(Value::from)),
             Self::Char(array) => Box::new(array.into_rows_rev().map(Value::from)),
             Self::Func(array) => Box::new(array.into_rows_rev().map(Value::from)),
@@ -115,6 +157,8 @@ impl Value {
     pub fn into_flat_values(self) -> Box<dyn Iterator<Item = Self>> {
         match self {
             Self::Num(array) => Box::new(array.data.into_iter().map(Value::from)),
+            Self::Complex(array) => Box::new(array.data.into_iter().map(Value::from)),
+            Self::Rational(array) => Box::new(array.data.into_iter().map(Value::from)),
             Self::Byte(array) => Box::new(array.data.into_iter().map(Value::from)),
             Self::Char(array) => Box::new(array.data.into_iter().map(Value::from)),
             Self::Func(array) => Box::new(array.data.into_iter().map(Value::from)),
@@ -123,22 +167,78 @@ impl Value {
     pub fn type_name(&self) -> &'static str {
         match self {
             Self::Num(_) | Self::Byte(_) => "number",
+            Self::Complex(_) => "complex",
+            Self::Rational(_) => "rational",
             Self::Char(_) => "character",
             Self::Func(_) => "function",
         }
     }
+    /// Compare two values of any types
+    ///
+    /// This is a total order. Values of different types are ordered
+    /// real numbers < complex numbers < characters < functions and boxes.
+    /// Real numbers compare by value whether they are stored as numbers, bytes, or rationals,
+    /// and `NaN` is greater than every other number.
+    ///
+    /// Within a type, arrays are ordered by rank, then lexicographically by their elements,
+    /// then by the number of elements, then by shape.
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value::Num(a), Value::Num(b)) => a.cmp(b),
+            (Value::Complex(a), Value::Complex(b)) => a.cmp(b),
+            (Value::Rational(a), Value::Rational(b)) => a.cmp(b),
+            (Value::Byte(a), Value::Byte(b)) => a.cmp(b),
+            (Value::Char(a), Value::Char(b)) => a.cmp(b),
+            (Value::Func(a), Value::Func(b)) => a.cmp(b),
+            (Value::Num(a), Value::Byte(b)) => a.partial_cmp(b).unwrap(),
+            (Value::Byte(a), Value::Num(b)) => a.partial_cmp(b).unwrap(),
+            (Value::Rational(a), Value::Num(b)) => a.partial_cmp(b).unwrap(),
+            (Value::Num(a), Value::Rational(b)) => a.partial_cmp(b).unwrap(),
+            (Value::Rational(a), Value::Byte(b)) => a.partial_cmp(b).unwrap(),
+            (Value::Byte(a), Value::Rational(b)) => a.partial_cmp(b).unwrap(),
+            (a, b) => a.type_order().cmp(&b.type_order()),
+        }
+    }
+    /// Where the value goes when values of different types are ordered
+    ///
+    /// See [`ArrayValue::TYPE_ORDER`]
+    pub fn type_order(&self) -> u8 {
+        match self {
+            Value::Num(_) => f64::TYPE_ORDER,
+            Value::Byte(_) => u8::TYPE_ORDER,
+            Value::Complex(_) => Complex::TYPE_ORDER,
+            Value::Rational(_) => Rational::TYPE_ORDER,
+            Value::Char(_) => char::TYPE_ORDER,
+            Value::Func(_) => Arc::<Function>::TYPE_ORDER,
+        }
+    }
+    /// The shape of the value
+    ///
+    /// A scalar has an empty shape. A scalar box is looked through, so its
+    /// shape is the shape of its contents, but an array of boxes has the
+    /// shape of the outer array.
     pub fn shape(&self) -> &[usize] {
-        self.generic_ref(Array::shape, Array::shape, Array::shape, Array::shape)
+        self.generic_ref(
+            Array::shape,
+            Array::shape,
+            Array::shape,
+            Array::shape,
+            Array::shape,
+            Array::shape,
+        )
     }
     pub fn shape_prefixes_match(&self, other: &Self) -> bool {
         self.shape().iter().zip(other.shape()).all(|(a, b)| a == b)
     }
+    /// The number of rows, which is `1` for a scalar
     pub fn row_count(&self) -> usize {
         self.generic_ref(
             Array::row_count,
             Array::row_count,
             Array::row_count,
             Array::row_count,
+            Array::row_count,
+            Array::row_count,
         )
     }
     pub fn row_len(&self) -> usize {
@@ -147,6 +247,8 @@ impl Value {
             Array::row_len,
             Array::row_len,
             Array::row_len,
+            Array::row_len,
+            Array::row_len,
         )
     }
     pub fn flat_len(&self) -> usize {
@@ -155,11 +257,15 @@ impl Value {
             Array::flat_len,
             Array::flat_len,
             Array::flat_len,
+            Array::flat_len,
+            Array::flat_len,
         )
     }
     pub(crate) fn first_dim_zero(&self) -> Self {
         match self {
             Self::Num(array) => array.first_dim_zero().into(),
+            Self::Complex(array) => array.first_dim_zero().into(),
+            Self::Rational(array) => array.first_dim_zero().into(),
             Self::Byte(array) => array.first_dim_zero().into(),
             Self::Char(array) => array.first_dim_zero().into(),
             Self::Func(array) => array.first_dim_zero().into(),
@@ -171,14 +277,19 @@ impl Value {
             Array::format_shape,
             Array::format_shape,
             Array::format_shape,
+            Array::format_shape,
+            Array::format_shape,
         )
     }
+    /// The number of dimensions, which is `0` for a scalar
     pub fn rank(&self) -> usize {
         self.shape().len()
     }
     pub fn shape_mut(&mut self) -> &mut Shape {
         match self {
             Self::Num(array) => &mut array.shape,
+            Self::Complex(array) => &mut array.shape,
+            Self::Rational(array) => &mut array.shape,
             Self::Byte(array) => &mut array.shape,
             Self::Char(array) => &mut array.shape,
             Self::Func(array) => &mut array.shape,
@@ -190,6 +301,8 @@ impl Value {
             Array::validate_shape,
             Array::validate_shape,
             Array::validate_shape,
+            Array::validate_shape,
+            Array::validate_shape,
         )
     }
     pub fn row(&self, i: usize) -> Self {
@@ -198,21 +311,27 @@ impl Value {
             |arr| arr.row(i).into(),
             |arr| arr.row(i).into(),
             |arr| arr.row(i).into(),
+            |arr| arr.row(i).into(),
+            |arr| arr.row(i).into(),
         )
     }
     pub fn generic_into<T>(
         self,
         n: impl FnOnce(Array<f64>) -> T,
+        co: impl FnOnce(Array<Complex>) -> T,
+        r: impl FnOnce(Array<Rational>) -> T,
         b: impl FnOnce(Array<u8>) -> T,
         c: impl FnOnce(Array<char>) -> T,
         f: impl FnOnce(Array<Arc<Function>>) -> T,
     ) -> T {
         match self {
             Self::Num(array) => n(array),
+            Self::Complex(array) => co(array),
+            Self::Rational(array) => r(array),
             Self::Byte(array) => b(array),
             Self::Char(array) => c(array),
             Self::Func(array) => match array.into_constant() {
-                Ok(value) => value.generic_into(n, b, c, f),
+                Ok(value) => value.generic_into(n, co, r, b, c, f),
                 Err(array) => f(array),
             },
         }
@@ -220,47 +339,65 @@ impl Value {
     pub fn generic_ref<'a, T: 'a>(
         &'a self,
         n: impl FnOnce(&'a Array<f64>) -> T,
+        co: impl FnOnce(&'a Array<Complex>) -> T,
+        r: impl FnOnce(&'a Array<Rational>) -> T,
         b: impl FnOnce(&'a Array<u8>) -> T,
         c: impl FnOnce(&'a Array<char>) -> T,
         f: impl FnOnce(&'a Array<Arc<Function>>) -> T,
     ) -> T {
         match self {
             Self::Num(array) => n(array),
+            Self::Complex(array) => co(array),
+            Self::Rational(array) => r(array),
             Self::Byte(array) => b(array),
             Self::Char(array) => c(array),
             Self::Func(array) => {
                 if let Some(value) = array.as_constant() {
-                    value.generic_ref(n, b, c, f)
+                    value.generic_ref(n, co, r, b, c, f)
                 } else {
                     f(array)
                 }
             }
         }
     }
+    #[allow(clippy::too_many_arguments)]
     pub fn generic_ref_env<'a, T: 'a>(
         &'a self,
         n: impl FnOnce(&'a Array<f64>, &Uiua) -> UiuaResult<T>,
+        co: impl FnOnce(&'a Array<Complex>, &Uiua) -> UiuaResult<T>,
+        r: impl FnOnce(&'a Array<Rational>, &Uiua) -> UiuaResult<T>,
         b: impl FnOnce(&'a Array<u8>, &Uiua) -> UiuaResult<T>,
         c: impl FnOnce(&'a Array<char>, &Uiua) -> UiuaResult<T>,
         f: impl FnOnce(&'a Array<Arc<Function>>, &Uiua) -> UiuaResult<T>,
         env: &Uiua,
     ) -> UiuaResult<T> {
-        self.generic_ref(|a| n(a, env), |a| b(a, env), |a| c(a, env), |a| f(a, env))
+        self.generic_ref(
+            |a| n(a, env),
+            |a| co(a, env),
+            |a| r(a, env),
+            |a| b(a, env),
+            |a| c(a, env),
+            |a| f(a, env),
+        )
     }
     pub fn generic_mut<T>(
         &mut self,
         n: impl FnOnce(&mut Array<f64>) -> T,
+        co: impl FnOnce(&mut Array<Complex>) -> T,
+        r: impl FnOnce(&mut Array<Rational>) -> T,
         b: impl FnOnce(&mut Array<u8>) -> T,
         c: impl FnOnce(&mut Array<char>) -> T,
         f: impl FnOnce(&mut Array<Arc<Function>>) -> T,
     ) -> T {
         match self {
             Self::Num(array) => n(array),
+            Self::Complex(array) => co(array),
+            Self::Rational(array) => r(array),
             Self::Byte(array) => b(array),
             Self::Char(array) => c(array),
             Self::Func(array) => {
                 if let Some(value) = array.as_constant_mut() {
-                    value.generic_mut(n, b, c, f)
+                    value.generic_mut(n, co, r, b, c, f)
                 } else {
                     f(array)
                 }
@@ -271,6 +408,8 @@ impl Value {
     pub fn show(&self) -> String {
         match self {
             Self::Num(array) => array.grid_string(),
+            Self::Complex(array) => array.grid_string(),
+            Self::Rational(array) => array.grid_string(),
             Self::Byte(array) => array.grid_string(),
             Self::Char(array) => array.grid_string(),
             Self::Func(array) => array.grid_string(),
@@ -413,6 +552,138 @@ impl Value {
     pub fn as_integers(&self, env: &Uiua, requirement: &'static str) -> UiuaResult<Vec<isize>> {
         self.as_number_list(env, requirement, |f| f.fract() == 0.0, |f| f as isize)
     }
+    /// Ensure that every number in the value is an integer
+    pub(crate) fn require_integers(&self, env: &Uiua, requirement: &'static str) -> UiuaResult {
+        self.require_numbers(env, requirement, false)
+    }
+    /// Ensure that every number in the value is a natural number
+    pub(crate) fn require_naturals(&self, env: &Uiua, requirement: &'static str) -> UiuaResult {
+        self.require_numbers(env, requirement, true)
+    }
+    /// Ensure that every number in the value is finite
+    pub(crate) fn require_finite(&self, env: &Uiua, requirement: &'static str) -> UiuaResult {
+        let not_finite = match self {
+            Value::Num(nums) => nums.data.iter().any(|n| !n.is_finite()),
+            Value::Complex(nums) => nums
+                .data
+                .iter()
+                .any(|n| !n.re.is_finite() || !n.im.is_finite()),
+            Value::Func(fs) => {
+                for f in &fs.data {
+                    if let Some(value) = f.as_constant() {
+                        value.require_finite(env, requirement)?;
+                    }
+                }
+                false
+            }
+            Value::Byte(_) | Value::Rational(_) | Value::Char(_) => false,
+        };
+        if not_finite {
+            return Err(env.error(format!("{requirement}, but it is not finite")));
+        }
+        Ok(())
+    }
+    /// Ensure that every number in the value fits in an `i64`
+    pub(crate) fn require_i64(&self, env: &Uiua, requirement: &'static str) -> UiuaResult {
+        let out_of_range = match self {
+            Value::Num(nums) => {
+                (nums.data.iter()).any(|&n| !(i64::MIN as f64 <= n && n < -(i64::MIN as f64)))
+            }
+            Value::Func(fs) => {
+                for f in &fs.data {
+                    if let Some(value) = f.as_constant() {
+                        value.require_i64(env, requirement)?;
+                    }
+                }
+                false
+            }
+            Value::Byte(_) | Value::Rational(_) | Value::Complex(_) | Value::Char(_) => false,
+        };
+        if out_of_range {
+            return Err(env.error(format!("{requirement}, but it is out of range")));
+        }
+        Ok(())
+    }
+    /// Ensure that every number in the value is small enough to be represented exactly
+    pub(crate) fn require_exact(&self, env: &Uiua, requirement: &'static str) -> UiuaResult {
+        const MAX_EXACT: f64 = (1u64 << f64::MANTISSA_DIGITS) as f64;
+        let inexact = match self {
+            Value::Num(nums) => nums.data.iter().any(|n| n.is_nan() || n.abs() > MAX_EXACT),
+            Value::Func(fs) => {
+                for f in &fs.data {
+                    if let Some(value) = f.as_constant() {
+                        value.require_exact(env, requirement)?;
+                    }
+                }
+                false
+            }
+            Value::Byte(_) | Value::Rational(_) | Value::Complex(_) | Value::Char(_) => false,
+        };
+        if inexact {
+            return Err(env.error(format!("{requirement}, but it is too large")));
+        }
+        Ok(())
+    }
+    /// Ensure that no number in the value is zero
+    pub(crate) fn require_nonzero(&self, env: &Uiua, requirement: &'static str) -> UiuaResult {
+        let has_zero = match self {
+            Value::Num(nums) => nums.data.contains(&0.0),
+            Value::Byte(bytes) => bytes.data.contains(&0),
+            Value::Rational(rats) => rats.data.contains(&Rational::ZERO),
+            Value::Func(fs) => {
+                for f in &fs.data {
+                    if let Some(value) = f.as_constant() {
+                        value.require_nonzero(env, requirement)?;
+                    }
+                }
+                false
+            }
+            Value::Complex(_) | Value::Char(_) => false,
+        };
+        if has_zero {
+            return Err(env.error(requirement));
+        }
+        Ok(())
+    }
+    /// Convert an array of numbers to complex if any of them are negative
+    pub(crate) fn complex_if_negative(self) -> Self {
+        match self {
+            Value::Num(nums) if nums.data.iter().any(|&n| n < 0.0) => {
+                nums.convert::<Complex>().into()
+            }
+            value => value,
+        }
+    }
+    fn require_numbers(&self, env: &Uiua, requirement: &'static str, natural: bool) -> UiuaResult {
+        match self {
+            Value::Num(nums) => {
+                if nums.data.iter().any(|n| n.fract().abs() > f64::EPSILON) {
+                    return Err(env.error(format!("{requirement}, but it has a fractional part")));
+                }
+                if natural && nums.data.iter().any(|&n| n < 0.0) {
+                    return Err(env.error(format!("{requirement}, but it is negative")));
+                }
+            }
+            Value::Func(fs) => {
+                for f in &fs.data {
+                    if let Some(value) = f.as_constant() {
+                        value.require_numbers(env, requirement, natural)?;
+                    }
+                }
+            }
+            Value::Complex(_) => return Err(env.error(format!("{requirement}, but it is complex"))),
+            Value::Rational(rats) => {
+                if rats.data.iter().any(|r| r.denom() != 1) {
+                    return Err(env.error(format!("{requirement}, but it has a fractional part")));
+                }
+                if natural && rats.data.iter().any(|r| r.numer() < 0) {
+                    return Err(env.error(format!("{requirement}, but it is negative")));
+                }
+            }
+            Value::Byte(_) | Value::Char(_) => {}
+        }
+        Ok(())
+    }
     pub(crate) fn as_number_list<T>(
         &self,
         env: &Uiua,
@@ -567,6 +838,8 @@ impl Value {
     pub fn coerce_to_function(self) -> Array<Arc<Function>> {
         match self {
             Value::Num(arr) => arr.convert_with(|n| Arc::new(Function::constant(n))),
+            Value::Complex(arr) => arr.convert_with(|n| Arc::new(Function::constant(n))),
+            Value::Rational(arr) => arr.convert_with(|n| Arc::new(Function::constant(n))),
             Value::Byte(arr) => arr.convert_with(|n| Arc::new(Function::constant(n))),
             Value::Char(arr) => arr.convert_with(|n| Arc::new(Function::constant(n))),
             Value::Func(arr) => arr,
@@ -577,6 +850,12 @@ impl Value {
             Value::Num(arr) => {
                 Cow::Owned(arr.convert_ref_with(|n| Arc::new(Function::constant(n))))
             }
+            Value::Complex(arr) => {
+                Cow::Owned(arr.convert_ref_with(|n| Arc::new(Function::constant(n))))
+            }
+            Value::Rational(arr) => {
+                Cow::Owned(arr.convert_ref_with(|n| Arc::new(Function::constant(n))))
+            }
             Value::Byte(arr) => {
                 Cow::Owned(arr.convert_ref_with(|n| Arc::new(Function::constant(n))))
             }
@@ -586,6 +865,79 @@ impl Value {
             Value::Func(arr) => Cow::Borrowed(arr),
         }
     }
+    /// Serialize the value as a JSON string
+    ///
+    /// Character lists become strings, and other arrays become nested lists.
+    pub fn to_json(&self) -> String {
+        self.to_json_value().to_string()
+    }
+    pub(crate) fn to_json_value(&self) -> serde_json::Value {
+        if self.rank() == 0 {
+            return match self {
+                Value::Num(nums) => json_number(nums.data[0]),
+                Value::Complex(nums) => serde_json::Value::Array(vec![
+                    json_number(nums.data[0].re),
+                    json_number(nums.data[0].im),
+                ]),
+                Value::Rational(rats) => json_number(rats.data[0].to_f64()),
+                Value::Byte(bytes) => bytes.data[0].into(),
+                Value::Char(chars) => chars.data[0].to_string().into(),
+                Value::Func(fs) => match fs.data[0].as_constant() {
+                    Some(value) => value.to_json_value(),
+                    None => fs.data[0].to_string().into(),
+                },
+            };
+        }
+        if let Value::Char(chars) = self {
+            if chars.rank() == 1 {
+                return chars.data.iter().collect::<String>().into();
+            }
+        }
+        serde_json::Value::Array(self.rows().map(|row| row.to_json_value()).collect())
+    }
+    /// Parse a JSON string into a value
+    ///
+    /// Lists whose items all have the same type and shape become arrays.
+    /// Other lists become lists of boxes.
+    pub fn from_json(s: &str, env: &Uiua) -> UiuaResult<Self> {
+        let json: serde_json::Value =
+            serde_json::from_str(s).map_err(|e| env.error(format!("Invalid JSON: {e}")))?;
+        Self::from_json_value(json, env)
+    }
+    fn from_json_value(json: serde_json::Value, env: &Uiua) -> UiuaResult<Self> {
+        use serde_json::Value as Json;
+        Ok(match json {
+            Json::Null => f64::NAN.into(),
+            Json::Bool(b) => b.into(),
+            Json::Number(n) => n.as_f64().unwrap_or(f64::NAN).into(),
+            Json::String(s) => s.into(),
+            Json::Array(items) => {
+                let values = (items.into_iter())
+                    .map(|item| Self::from_json_value(item, env))
+                    .collect::<UiuaResult<Vec<_>>>()?;
+                let uniform = values.windows(2).all(|pair| {
+                    pair[0].type_name() == pair[1].type_name() && pair[0].shape() == pair[1].shape()
+                });
+                if uniform {
+                    Value::from_row_values(values, env)?
+                } else {
+                    let boxes = values
+                        .into_iter()
+                        .map(|value| Arc::new(Function::constant(value)));
+                    Value::from_row_values(boxes, env)?
+                }
+            }
+            Json::Object(_) => return Err(env.error("JSON objects are not supported yet")),
+        })
+    }
+}
+
+fn json_number(n: f64) -> serde_json::Value {
+    if n.fract() == 0.0 && n.abs() < 2f64.powi(53) {
+        (n as i64).into()
+    } else {
+        serde_json::Number::from_f64(n).map_or(serde_json::Value::Null, Into::into)
+    }
 }
 
 macro_rules! value_from {
@@ -619,6 +971,8 @@ macro_rules! value_from {
 }
 
 value_from!(f64, Num);
+value_from!(Complex, Complex);
+value_from!(Rational, Rational);
 value_from!(u8, Byte);
 value_from!(char, Char);
 value_from!(Arc<Function>, Func);
@@ -697,7 +1051,24 @@ macro_rules! value_un_impl_all {
     }
 }
 
-value_un_impl_all!(neg, not, abs, sign, sqrt, sin, cos, tan, asin, acos, floor, ceil, round);
+value_un_impl_all!(not, sin, cos, tan, asin, acos, floor, ceil, round, halfeven, factorial, bitnot);
+
+macro_rules! value_un_impl_complex {
+    ($($name:ident),* $(,)?) => {
+        $(value_un_impl!($name, (Num, num), (Byte, byte), (Complex, com));)*
+    }
+}
+
+value_un_impl_complex!(sqrt, real, imag);
+
+macro_rules! value_un_impl_exact {
+    ($($name:ident),* $(,)?) => {
+        $(value_un_impl!($name, (Num, num), (Byte, byte), (Complex, com), (Rational, rat));)*
+    }
+}
+
+value_un_impl_exact!(neg, abs, sign, isnan, isinf);
+value_un_impl!(float, (Num, num), (Byte, byte), (Rational, rat));
 
 macro_rules! val_retry {
     (Byte, $env:expr) => {
@@ -769,6 +1140,16 @@ value_bin_impl!(
     (Char, Byte, char_byte),
     (Byte, Num, byte_num, num_num),
     (Num, Byte, num_byte, num_num),
+    (Complex, Complex, com_com),
+    (Complex, Num, com_num),
+    (Num, Complex, num_com),
+    (Complex, Byte, com_byte),
+    (Byte, Complex, byte_com),
+    (Rational, Rational, rat_rat),
+    (Rational, Byte, rat_byte),
+    (Byte, Rational, byte_rat),
+    (Rational, Num, rat_num),
+    (Num, Rational, num_rat),
 );
 
 value_bin_impl!(
@@ -780,6 +1161,16 @@ value_bin_impl!(
     (Byte, Char, byte_char),
     (Byte, Num, byte_num, num_num),
     (Num, Byte, num_byte, num_num),
+    (Complex, Complex, com_com),
+    (Complex, Num, com_num),
+    (Num, Complex, num_com),
+    (Complex, Byte, com_byte),
+    (Byte, Complex, byte_com),
+    (Rational, Rational, rat_rat),
+    (Rational, Byte, rat_byte),
+    (Byte, Rational, byte_rat),
+    (Rational, Num, rat_num),
+    (Num, Rational, num_rat),
 );
 
 value_bin_impl!(
@@ -788,6 +1179,16 @@ value_bin_impl!(
     (Byte, Byte, byte_byte, num_num),
     (Byte, Num, byte_num, num_num),
     (Num, Byte, num_byte, num_num),
+    (Complex, Complex, com_com),
+    (Complex, Num, com_num),
+    (Num, Complex, num_com),
+    (Complex, Byte, com_byte),
+    (Byte, Complex, byte_com),
+    (Rational, Rational, rat_rat),
+    (Rational, Byte, rat_byte),
+    (Byte, Rational, byte_rat),
+    (Rational, Num, rat_num),
+    (Num, Rational, num_rat),
 );
 value_bin_impl!(
     div,
@@ -795,6 +1196,16 @@ value_bin_impl!(
     (Byte, Byte, byte_byte, num_num),
     (Byte, Num, byte_num, num_num),
     (Num, Byte, num_byte, num_num),
+    (Complex, Complex, com_com),
+    (Complex, Num, com_num),
+    (Num, Complex, num_com),
+    (Complex, Byte, com_byte),
+    (Byte, Complex, byte_com),
+    (Rational, Rational, rat_rat),
+    (Rational, Byte, rat_byte),
+    (Byte, Rational, byte_rat),
+    (Rational, Num, rat_num),
+    (Num, Rational, num_rat),
 );
 value_bin_impl!(
     modulus,
@@ -809,6 +1220,11 @@ value_bin_impl!(
     (Byte, Byte, byte_byte, num_num),
     (Byte, Num, byte_num, num_num),
     (Num, Byte, num_byte, num_num),
+    (Complex, Complex, com_com),
+    (Complex, Num, com_num),
+    (Num, Complex, num_com),
+    (Complex, Byte, com_byte),
+    (Byte, Complex, byte_com),
 );
 value_bin_impl!(
     log,
@@ -818,6 +1234,20 @@ value_bin_impl!(
     (Num, Byte, num_byte, num_num),
 );
 value_bin_impl!(atan2, (Num, Num, num_num));
+value_bin_impl!(
+    rational,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte),
+    (Byte, Num, byte_num),
+    (Num, Byte, num_byte),
+);
+value_bin_impl!(
+    complex,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte),
+    (Byte, Num, byte_num),
+    (Num, Byte, num_byte),
+);
 
 value_bin_impl!(
     min,
@@ -837,6 +1267,94 @@ value_bin_impl!(
     (Num, Byte, num_byte, num_num),
 );
 
+value_bin_impl!(
+    gcd,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    lcm,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    nearest,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    below,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    above,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    nanfill,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    and,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    or,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    bitand,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    bitor,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
+value_bin_impl!(
+    bitxor,
+    (Num, Num, num_num),
+    (Byte, Byte, byte_byte, num_num),
+    (Byte, Num, byte_num, num_num),
+    (Num, Byte, num_byte, num_num),
+);
+
 macro_rules! cmp_impls {
     ($($name:ident),*) => {
         $(
@@ -849,11 +1367,27 @@ macro_rules! cmp_impls {
                 (Func, Func, generic),
                 (Num, Byte, num_byte, num_num),
                 (Byte, Num, byte_num, num_num),
+                (Complex, Complex, com_com),
+                (Complex, Num, com_num),
+                (Num, Complex, num_com),
+                (Complex, Byte, com_byte),
+                (Byte, Complex, byte_com),
+                (Rational, Rational, generic),
+                (Rational, Byte, rat_byte),
+                (Byte, Rational, byte_rat),
+                (Rational, Num, rat_num),
+                (Num, Rational, num_rat),
                 // Type comparable
-                (Num, Char, always_less),
-                (Byte, Char, always_less),
-                (Char, Num, always_greater),
-                (Char, Byte, always_greater),
+                (Num, Char, by_type),
+                (Byte, Char, by_type),
+                (Char, Num, by_type),
+                (Char, Byte, by_type),
+                (Complex, Char, by_type),
+                (Char, Complex, by_type),
+                (Rational, Char, by_type),
+                (Char, Rational, by_type),
+                (Complex, Rational, by_type),
+                (Rational, Complex, by_type),
             );
         )*
     };
@@ -861,10 +1395,77 @@ macro_rules! cmp_impls {
 
 cmp_impls!(is_eq, is_ne, is_lt, is_le, is_gt, is_ge);
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for Value {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Num(array) => serializer.serialize_newtype_variant("Value", 0, "Num", array),
+            Value::Byte(array) => serializer.serialize_newtype_variant("Value", 1, "Byte", array),
+            Value::Char(array) => serializer.serialize_newtype_variant("Value", 2, "Char", array),
+            Value::Complex(array) => {
+                serializer.serialize_newtype_variant("Value", 4, "Complex", array)
+            }
+            Value::Rational(array) => {
+                serializer.serialize_newtype_variant("Value", 5, "Rational", array)
+            }
+            Value::Func(array) => {
+                let mut boxed = Vec::with_capacity(array.flat_len());
+                for f in &array.data {
+                    let value = f.as_constant().ok_or_else(|| {
+                        serde::ser::Error::custom("functions cannot be serialized, only boxes")
+                    })?;
+                    boxed.push(value.clone());
+                }
+                let boxed = Array::new(array.shape.clone(), boxed);
+                serializer.serialize_newtype_variant("Value", 3, "Box", &boxed)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Value {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Value")]
+        enum Rep {
+            Num(Array<f64>),
+            Byte(Array<u8>),
+            Char(Array<char>),
+            Box(Array<Value>),
+            Complex(Array<Complex>),
+            Rational(Array<Rational>),
+        }
+        Ok(match Rep::deserialize(deserializer)? {
+            Rep::Num(array) => array.into(),
+            Rep::Byte(array) => array.into(),
+            Rep::Char(array) => array.into(),
+            Rep::Complex(array) => array.into(),
+            Rep::Rational(array) => array.into(),
+            Rep::Box(array) => {
+                let boxes: Vec<_> = (array.data.into_iter())
+                    .map(|value| Arc::new(Function::constant(value)))
+                    .collect();
+                Array::new(array.shape, boxes).into()
+            }
+        })
+    }
+}
+
+/// Structural equality
+///
+/// Values are equal if they have the same shape and their elements are equal.
+/// Unlike IEEE comparison, `NaN` equals `NaN`, so that this can be [`Eq`].
+/// Numbers and bytes with the same elements are equal.
+///
+/// This is the equality used by [`Primitive::Match`](crate::primitive::Primitive::Match).
+/// The pervasive [`Primitive::Eq`](crate::primitive::Primitive::Eq) also treats `NaN` as equal to itself.
 impl PartialEq for Value {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Value::Num(a), Value::Num(b)) => a == b,
+            (Value::Complex(a), Value::Complex(b)) => a == b,
+            (Value::Rational(a), Value::Rational(b)) => a == b,
             (Value::Byte(a), Value::Byte(b)) => a == b,
             (Value::Char(a), Value::Char(b)) => a == b,
             (Value::Func(a), Value::Func(b)) => a == b,
@@ -885,20 +1486,7 @@ impl PartialOrd for Value {
 
 impl Ord for Value {
     fn cmp(&self, other: &Self) -> Ordering {
-        match (self, other) {
-            (Value::Num(a), Value::Num(b)) => a.cmp(b),
-            (Value::Byte(a), Value::Byte(b)) => a.cmp(b),
-            (Value::Char(a), Value::Char(b)) => a.cmp(b),
-            (Value::Func(a), Value::Func(b)) => a.cmp(b),
-            (Value::Num(a), Value::Byte(b)) => a.partial_cmp(b).unwrap(),
-            (Value::Byte(a), Value::Num(b)) => a.partial_cmp(b).unwrap(),
-            (Value::Num(_), _) => Ordering::Less,
-            (_, Value::Num(_)) => Ordering::Greater,
-            (Value::Byte(_), _) => Ordering::Less,
-            (_, Value::Byte(_)) => Ordering::Greater,
-            (Value::Char(_), _) => Ordering::Less,
-            (_, Value::Char(_)) => Ordering::Greater,
-        }
+        self.total_cmp(other)
     }
 }
 
@@ -909,9 +1497,11 @@ impl Hash for Value {
                 0u8.hash(state);
                 arr.hash(state);
             }
+            // Bytes hash like the numbers they are equal to
             Value::Byte(arr) => {
-                1u8.hash(state);
-                arr.hash(state);
+                0u8.hash(state);
+                arr.shape.hash(state);
+                (arr.data.iter()).for_each(|&b| f64::from(b).array_hash(state));
             }
             Value::Char(arr) => {
                 2u8.hash(state);
@@ -921,6 +1511,14 @@ impl Hash for Value {
                 3u8.hash(state);
                 arr.hash(state);
             }
+            Value::Complex(arr) => {
+                4u8.hash(state);
+                arr.hash(state);
+            }
+            Value::Rational(arr) => {
+                5u8.hash(state);
+                arr.hash(state);
+            }
         }
     }
 }
@@ -929,6 +1527,8 @@ impl fmt::Display for Value {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Value::Num(n) => n.fmt(f),
+            Value::Complex(n) => n.fmt(f),
+            Value::Rational(n) => n.fmt(f),
             Value::Byte(b) => b.fmt(f),
             Value::Char(c) => c.fmt(f),
             Value::Func(func) => {
@@ -941,3 +1541,173 @@ impl fmt::Display for Value {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_output() {
+        let mut env = Uiua::with_native_sys();
+        env.load_str(r#"[1 2 3] [[1 2] [3 4]] "hi" [1.5 ¯2]"#)
+            .unwrap();
+        let json: Vec<String> = env.take_stack().iter().map(Value::to_json).collect();
+        assert_eq!(json, ["[1.5,-2]", r#""hi""#, "[[1,2],[3,4]]", "[1,2,3]"]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut env = Uiua::with_native_sys();
+        env.load_str(r#"5 "hello" ↯2_3⇡6 [□1 □"two" □[3 4 5]]"#)
+            .unwrap();
+        for value in env.take_stack() {
+            let json = serde_json::to_string(&value).unwrap();
+            let round_tripped: Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(value, round_tripped, "{json}");
+        }
+    }
+
+    #[test]
+    fn value_builders() {
+        let matrix = Value::num_array(&[2, 3], [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(matrix.shape(), [2, 3]);
+        let rows = Value::from_rows([vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+        assert_eq!(rows, matrix);
+        let nested = Value::from_rows([
+            Value::from_rows([Value::scalar(1.0), Value::scalar(2.0)]),
+            Value::from_rows([Value::scalar(3.0), Value::scalar(4.0)]),
+        ]);
+        assert_eq!(nested.shape(), [2, 2]);
+        let mut env = Uiua::with_native_sys();
+        env.load_str("[1_2_3 4_5_6]").unwrap();
+        assert_eq!(env.pop(1).unwrap(), rows);
+        assert_eq!(Value::from_rows(Vec::<Value>::new()).shape(), [0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not match data length")]
+    fn value_builder_bad_shape() {
+        Value::num_array(&[2, 2], [1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot couple arrays with shapes [2] and [1]")]
+    fn value_builder_ragged_rows() {
+        Value::from_rows([vec![1.0, 2.0], vec![3.0]]);
+    }
+
+    #[test]
+    fn major_cells() {
+        let array = Value::num_array(&[3, 2], [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        let rows: Vec<Value> = array.rows().collect();
+        assert_eq!(rows.len(), 3);
+        for row in &rows {
+            assert_eq!(row.shape(), [2]);
+        }
+        assert_eq!(rows[1], Value::from(vec![3.0, 4.0]));
+        let nums = array.as_num_array().unwrap();
+        let slices: Vec<&[f64]> = nums.row_slices().collect();
+        assert_eq!(slices, [[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+        assert_eq!(Value::from_rows(array.clone().into_rows()), array);
+    }
+
+    #[test]
+    fn value_total_order() {
+        let values = [
+            Value::scalar(f64::NAN),
+            Value::from('a'),
+            Value::from(Function::constant(3.0)),
+            Value::num_array(&[2, 1], [1.0, 2.0]),
+            Value::from(2u8),
+            Value::from(Complex::I),
+            Value::scalar(-1.0),
+            Value::from("ab"),
+            Value::from(Function::constant('a')),
+            Value::from(Rational::new(1, 2)),
+            Value::scalar(0.75),
+            Value::num_array(&[1, 2], [1.0, 2.0]),
+            Value::scalar(0.5),
+            Value::scalar(2.0),
+            Value::from(Function::constant(1.0)),
+        ];
+        for a in &values {
+            for b in &values {
+                let ab = a.total_cmp(b);
+                assert_eq!(ab, b.total_cmp(a).reverse(), "{a:?} {b:?}");
+                assert_eq!(ab == Ordering::Equal, a == b, "{a:?} {b:?}");
+                for c in &values {
+                    if ab != Ordering::Greater && b.total_cmp(c) != Ordering::Greater {
+                        assert_ne!(a.total_cmp(c), Ordering::Greater, "{a:?} {b:?} {c:?}");
+                    }
+                }
+            }
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort();
+        let mut reversed = values.to_vec();
+        reversed.reverse();
+        reversed.sort();
+        assert_eq!(sorted, reversed);
+        assert_eq!(sorted[0], Value::scalar(-1.0));
+        assert_eq!(
+            sorted[1..4],
+            [
+                Value::scalar(0.5),
+                Value::from(Rational::new(1, 2)),
+                Value::scalar(0.75)
+            ]
+        );
+        assert!(matches!(&sorted[6], Value::Num(n) if n.data[0].is_nan()));
+    }
+
+    #[test]
+    fn shape_rank_and_length() {
+        let eval = |code: &str| {
+            let mut env = Uiua::with_native_sys();
+            env.load_str(code).unwrap();
+            env.pop(1).unwrap()
+        };
+        let check = |value: &Value, shape: &[usize], rank: usize, rows: usize| {
+            assert_eq!(value.shape(), shape, "{value:?}");
+            assert_eq!(value.rank(), rank, "{value:?}");
+            assert_eq!(value.row_count(), rows, "{value:?}");
+        };
+        check(&Value::scalar(5.0), &[], 0, 1);
+        check(&Value::from(vec![1.0, 2.0, 3.0]), &[3], 1, 3);
+        check(&Value::num_array(&[2, 3], [0.0; 6]), &[2, 3], 2, 2);
+        check(&Value::from(Vec::<f64>::new()), &[0], 1, 0);
+        check(&eval("[□1 □[2 3]]"), &[2], 1, 2);
+        check(&eval("[□[1 2] □[3 4]]"), &[2], 1, 2);
+        check(&eval("□[1 2 3]"), &[3], 1, 3);
+        check(&eval("□5"), &[], 0, 1);
+    }
+
+    #[test]
+    fn value_equality() {
+        let nan = Value::scalar(f64::NAN);
+        assert_eq!(nan, nan.clone());
+        assert_eq!(Value::scalar(-0.0), Value::scalar(0.0));
+        assert_eq!(Value::from(2u8), Value::scalar(2.0));
+        let hash = |value: &Value| {
+            use std::hash::{Hash, Hasher};
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(
+            hash(&Value::from(vec![1u8, 2])),
+            hash(&Value::from(vec![1.0, 2.0]))
+        );
+        assert_eq!(hash(&Value::scalar(-0.0)), hash(&Value::scalar(0.0)));
+        assert_ne!(Value::from(vec![1.0, 2.0]), Value::scalar(1.0));
+        assert_ne!(
+            Value::num_array(&[2, 1], [1.0, 2.0]),
+            Value::num_array(&[1, 2], [1.0, 2.0])
+        );
+        assert_ne!(Value::from("a"), Value::from('a'));
+        let mut env = Uiua::with_native_sys();
+        env.load_str("= ÷0 0 ÷0 0\n≍ ÷0 0 ÷0 0").unwrap();
+        assert_eq!(env.take_stack(), [Value::from(1u8), Value::from(1u8)]);
+    }
+}
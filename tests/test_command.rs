@@ -0,0 +1,44 @@
+#![cfg(feature = "binary")]
+
+use std::{fs, process::Command};
+
+#[test]
+fn test_command_summary() {
+    let dir = std::env::temp_dir().join("uiua_test_command");
+    fs::create_dir_all(&dir).unwrap();
+
+    let path = dir.join("mixed.ua");
+    fs::write(
+        &path,
+        "~~~\n⍤\"one\" =1 1\n⍤\"two\" =1 2\n⍤\"three\" =2 2\n⍤\"four\" =3 4\n~~~\n",
+    )
+    .unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_uiua"))
+        .arg("test")
+        .arg(&path)
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(output.status.code(), Some(1), "{stderr}");
+    assert!(stderr.contains("2 assertions passed, 2 failed"), "{stderr}");
+    assert!(stderr.contains("mixed.ua:3:"), "{stderr}");
+    assert!(stderr.contains("mixed.ua:5:"), "{stderr}");
+    assert!(!stderr.contains("mixed.ua:2:"), "{stderr}");
+    assert!(!stderr.contains("mixed.ua:4:"), "{stderr}");
+
+    let path = dir.join("passing.ua");
+    fs::write(&path, "~~~\n⍤\"one\" =1 1\n⍤\"two\" =2 2\n~~~\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_uiua"))
+        .arg("test")
+        .arg(&path)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains("No failures! (2 assertions passed)"),
+        "{stdout}"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}